@@ -0,0 +1,341 @@
+use std::path::{Path, PathBuf};
+
+use clap::{Parser, Subcommand, ValueEnum};
+use color_recall::css::{parse_css_color, to_css, CssFormat};
+use color_recall::exclusion::ExclusionPreset;
+use color_recall::game::{
+    format_hex, ChallengeSeed, ColorChallenge, ColorChooser, HSLChooser, HSLUVChooser, HSVChooser,
+    HWBChooser, LABChooser, LCHChooser, LCHUVChooser, LUVChooser, OklabChooser, OklchChooser,
+    RGB8Chooser, RGBChooser, XYZChooser,
+};
+use color_recall::metric::{DifferenceMetric, Metric};
+use color_recall::practice::PracticePalette;
+use color_recall::sampling::TargetDistribution;
+use color_recall::sequence::{score_sequence, Matching, SequenceChallenge, MAX_COLORS};
+use palette::{IntoColor, Lab, Srgb};
+use rand::rngs::OsRng;
+
+/// Color recall scoring and conversions for scripts.
+#[derive(Parser)]
+#[command(version)]
+struct Cli {
+    #[command(subcommand)]
+    command: Command,
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// Print the difference between a target and a guess.
+    Score {
+        /// Target color, a hex code or any CSS color such as `oklch(0.6 0.15 45)`.
+        #[arg(value_parser = parse_color)]
+        target: Srgb,
+        /// Guessed color, a hex code or CSS color.
+        #[arg(value_parser = parse_color)]
+        guess: Srgb,
+        /// Metrics to report, defaults to the game's metric. One of cie76, cie94_graphic_arts,
+        /// cie94_textiles, cmc, ciede2000, ciede2000_improved, oklab or itp.
+        #[arg(long, value_parser = parse_metric)]
+        metric: Vec<Metric>,
+        /// Report every metric.
+        #[arg(long, conflicts_with = "metric")]
+        all: bool,
+    },
+    /// Print the difference of each color of a palette from its recalled color, then the mean.
+    Palette {
+        /// Target colors, comma separated hex codes or CSS colors in the modern space separated syntax.
+        #[arg(long, required = true, value_delimiter = ',', value_parser = parse_color)]
+        targets: Vec<Srgb>,
+        /// Recalled colors in the same order, comma separated.
+        #[arg(long, required = true, value_delimiter = ',', value_parser = parse_color)]
+        guesses: Vec<Srgb>,
+        /// Pair guesses with the targets they match best instead of by position.
+        #[arg(long)]
+        any_order: bool,
+        /// Metric to use, defaults to the game's metric.
+        #[arg(long, value_parser = parse_metric)]
+        metric: Option<Metric>,
+    },
+    /// Print a color as slider values of the game's color models.
+    Convert {
+        /// Color to convert, a hex code or CSS color.
+        #[arg(value_parser = parse_color)]
+        color: Srgb,
+        /// Models to convert to, defaults to all of them unless `--css` is given.
+        #[arg(long, value_enum)]
+        to: Vec<Model>,
+        /// CSS syntaxes to write the color in: hex, rgb, hsl, hwb, lab, lch, oklab, oklch or
+        /// display_p3.
+        #[arg(long, value_parser = parse_css_format)]
+        css: Vec<CssFormat>,
+    },
+    /// Print challenge targets, one `seed #rrggbb…` per line.
+    Generate {
+        /// First seed as 16 hex digits, the following ones are drawn from it as in the game.
+        #[arg(long)]
+        seed: Option<ChallengeSeed>,
+        /// Number of challenges.
+        #[arg(long, default_value_t = 1)]
+        count: usize,
+        /// Colors per challenge, more than one generates palettes to memorize together.
+        #[arg(long, default_value_t = 1, value_parser = clap::value_parser!(u8).range(1..=MAX_COLORS as i64))]
+        colors: u8,
+        /// Exclusion policy: default, strict, none or display_robust.
+        #[arg(long, default_value = ExclusionPreset::default().key(), value_parser = parse_exclusion)]
+        exclusion: ExclusionPreset,
+        /// Space targets are uniformly drawn from: srgb, lab or oklab.
+        #[arg(long, default_value = TargetDistribution::default().key(), value_parser = parse_distribution)]
+        distribution: TargetDistribution,
+        /// Draw targets from a palette file instead, a JSON object of names to CSS colors, a JSON
+        /// array of CSS colors or a stylesheet of custom properties. Prints `seed #rrggbb name`.
+        #[arg(long, conflicts_with_all = ["colors", "distribution"])]
+        palette: Option<PathBuf>,
+    },
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, ValueEnum)]
+enum Model {
+    Srgb,
+    Rgb8,
+    Hex,
+    Hsv,
+    Hsl,
+    Hwb,
+    Lab,
+    Xyz,
+    Lch,
+    Oklab,
+    Oklch,
+    Luv,
+    Lchuv,
+    Hsluv,
+}
+
+impl Model {
+    fn key(self) -> &'static str {
+        match self {
+            Model::Srgb => "srgb",
+            Model::Rgb8 => "rgb8",
+            Model::Hex => "hex",
+            Model::Hsv => "hsv",
+            Model::Hsl => "hsl",
+            Model::Hwb => "hwb",
+            Model::Lab => "lab",
+            Model::Xyz => "xyz",
+            Model::Lch => "lch",
+            Model::Oklab => "oklab",
+            Model::Oklch => "oklch",
+            Model::Luv => "luv",
+            Model::Lchuv => "lchuv",
+            Model::Hsluv => "hsluv",
+        }
+    }
+
+    fn values_for(self, srgb: Srgb) -> Vec<String> {
+        let sliders = match self {
+            Model::Srgb => RGBChooser::from_srgb(srgb),
+            Model::Rgb8 => {
+                return RGB8Chooser::from_srgb(srgb)
+                    .iter()
+                    .map(|s| s.value.to_string())
+                    .collect()
+            }
+            Model::Hex => return vec![format_hex(srgb)],
+            Model::Hsv => HSVChooser::from_srgb(srgb),
+            Model::Hsl => HSLChooser::from_srgb(srgb),
+            Model::Hwb => HWBChooser::from_srgb(srgb),
+            Model::Lab => LABChooser::from_srgb(srgb),
+            Model::Xyz => XYZChooser::from_srgb(srgb),
+            Model::Lch => LCHChooser::from_srgb(srgb),
+            Model::Oklab => OklabChooser::from_srgb(srgb),
+            Model::Oklch => OklchChooser::from_srgb(srgb),
+            Model::Luv => LUVChooser::from_srgb(srgb),
+            Model::Lchuv => LCHUVChooser::from_srgb(srgb),
+            Model::Hsluv => HSLUVChooser::from_srgb(srgb),
+        };
+        sliders.iter().map(|s| format!("{:.4}", s.value)).collect()
+    }
+}
+
+fn parse_color(s: &str) -> Result<Srgb, String> {
+    parse_css_color(s).map_err(|err| err.to_string())
+}
+
+fn parse_css_format(key: &str) -> Result<CssFormat, String> {
+    CssFormat::from_key(key).ok_or_else(|| format!("unknown CSS format {:?}", key))
+}
+
+fn parse_metric(key: &str) -> Result<Metric, String> {
+    Metric::from_key(key).ok_or_else(|| format!("unknown metric {:?}", key))
+}
+
+fn parse_exclusion(key: &str) -> Result<ExclusionPreset, String> {
+    ExclusionPreset::from_key(key).ok_or_else(|| format!("unknown exclusion policy {:?}", key))
+}
+
+fn parse_distribution(key: &str) -> Result<TargetDistribution, String> {
+    TargetDistribution::from_key(key).ok_or_else(|| format!("unknown distribution {:?}", key))
+}
+
+fn score(target: Srgb, guess: Srgb, metrics: Vec<Metric>, all: bool) {
+    let target: Lab = target.into_color();
+    let guess: Lab = guess.into_color();
+
+    if all {
+        for metric in Metric::ALL {
+            println!("{} {:.4}", metric.key(), metric.difference(target, guess));
+        }
+    } else if metrics.len() > 1 {
+        for metric in metrics {
+            println!("{} {:.4}", metric.key(), metric.difference(target, guess));
+        }
+    } else {
+        let metric = metrics.first().copied().unwrap_or_default();
+        println!("{:.4}", metric.difference(target, guess));
+    }
+}
+
+fn palette(
+    targets: Vec<Srgb>,
+    guesses: Vec<Srgb>,
+    any_order: bool,
+    metric: Option<Metric>,
+) -> Result<(), String> {
+    let matching = if any_order {
+        Matching::BestMatch
+    } else {
+        Matching::Ordered
+    };
+    let score = score_sequence(&metric.unwrap_or_default(), &targets, &guesses, matching)
+        .ok_or_else(|| {
+            format!(
+                "expected as many guesses as targets, got {} targets and {} guesses",
+                targets.len(),
+                guesses.len()
+            )
+        })?;
+
+    for (target, (guess, distance)) in targets
+        .iter()
+        .zip(score.assignment.iter().zip(&score.distances))
+    {
+        println!(
+            "{} {} {:.4}",
+            format_hex(*target),
+            format_hex(guesses[*guess]),
+            distance
+        );
+    }
+    println!("mean {:.4}", score.mean());
+    Ok(())
+}
+
+fn convert(color: Srgb, to: Vec<Model>, css: Vec<CssFormat>) {
+    let models = if to.is_empty() && css.is_empty() {
+        Model::value_variants().to_vec()
+    } else {
+        to
+    };
+
+    for model in models {
+        println!("{} {}", model.key(), model.values_for(color).join(" "));
+    }
+    for format in css {
+        println!("css {}", to_css(color, format));
+    }
+}
+
+fn generate(
+    seed: ChallengeSeed,
+    count: usize,
+    colors: usize,
+    exclusion: ExclusionPreset,
+    distribution: TargetDistribution,
+) {
+    for seed in seed.sequence().take(count) {
+        let targets = match SequenceChallenge::from_seed_with(seed, colors, distribution, exclusion)
+        {
+            Some(challenge) => challenge.targets().to_vec(),
+            None => {
+                vec![*ColorChallenge::from_seed_with(seed, distribution, exclusion).target_color()]
+            }
+        };
+        let targets: Vec<String> = targets.into_iter().map(format_hex).collect();
+        println!("{} {}", seed, targets.join(" "));
+    }
+}
+
+fn load_palette(path: &Path) -> Result<PracticePalette, String> {
+    let text =
+        std::fs::read_to_string(path).map_err(|err| format!("{}: {}", path.display(), err))?;
+    let palette = if text.trim_start().starts_with(['{', '[']) {
+        PracticePalette::from_json(&text)
+    } else {
+        PracticePalette::from_css_variables(&text)
+    };
+    palette.map_err(|err| format!("{}: {}", path.display(), err))
+}
+
+fn practice(
+    seed: ChallengeSeed,
+    count: usize,
+    palette: &PracticePalette,
+    exclusion: ExclusionPreset,
+) {
+    for (entry, reason) in palette.excluded(&exclusion) {
+        eprintln!(
+            "warning: {} would not be drawn as a random target: {}",
+            entry.name, reason
+        );
+    }
+
+    for seed in seed.sequence().take(count) {
+        let entry = palette.draw(seed);
+        println!("{} {} {}", seed, format_hex(entry.color), entry.name);
+    }
+}
+
+fn main() {
+    let cli = Cli::parse();
+
+    match cli.command {
+        Command::Score {
+            target,
+            guess,
+            metric,
+            all,
+        } => score(target, guess, metric, all),
+        Command::Palette {
+            targets,
+            guesses,
+            any_order,
+            metric,
+        } => {
+            if let Err(err) = palette(targets, guesses, any_order, metric) {
+                eprintln!("error: {}", err);
+                std::process::exit(2);
+            }
+        }
+        Command::Convert { color, to, css } => convert(color, to, css),
+        Command::Generate {
+            seed,
+            count,
+            colors,
+            exclusion,
+            distribution,
+            palette,
+        } => {
+            let seed = seed.unwrap_or_else(|| ChallengeSeed::random(&mut OsRng));
+            match palette {
+                Some(path) => match load_palette(&path) {
+                    Ok(palette) => practice(seed, count, &palette, exclusion),
+                    Err(err) => {
+                        eprintln!("error: {}", err);
+                        std::process::exit(2);
+                    }
+                },
+                None => generate(seed, count, colors as usize, exclusion, distribution),
+            }
+        }
+    }
+}
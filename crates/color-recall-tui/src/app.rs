@@ -0,0 +1,291 @@
+use std::time::Duration;
+
+use color_recall::exclusion::{ExclusionPolicy, ExclusionPreset};
+use color_recall::game::{
+    ChallengeSeed, ColorChallenge, ColorChooser, ExcludeReason, HSLChooser, HSLUVChooser,
+    HSVChooser, HWBChooser, LABChooser, LCHChooser, LCHUVChooser, LUVChooser, OklabChooser,
+    OklchChooser, RGBChooser, Slider, XYZChooser,
+};
+use color_recall::gamut::GamutMapping;
+use color_recall::metric::Metric;
+use color_recall::phase::{GamePhase, GameRound, PhaseError, SystemClock};
+use color_recall::sampling::TargetDistribution;
+use color_recall::sequence::{Matching, Presentation, SequenceChallenge, SequenceRound};
+use color_recall::session::GameSession;
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+use palette::Srgb;
+
+/// Slider steps per full range for the arrow keys, shift moves 8 steps at once.
+const SLIDER_STEPS: f32 = 128.0;
+
+/// A chooser together with its sliders, erased so all models fit in one list.
+pub struct Model {
+    pub key: &'static str,
+    pub name: &'static str,
+    pub sliders: Box<[Slider<f32>]>,
+    init_sliders: fn() -> Box<[Slider<f32>]>,
+    as_srgb: fn(&[Slider<f32>], GamutMapping) -> Srgb,
+    in_gamut: fn(&[Slider<f32>]) -> bool,
+    from_srgb: fn(Srgb) -> Box<[Slider<f32>]>,
+}
+
+impl Model {
+    fn new<C: ColorChooser<f32>>(key: &'static str, name: &'static str) -> Self {
+        Model {
+            key,
+            name,
+            sliders: C::default().init_sliders(),
+            init_sliders: || C::default().init_sliders(),
+            as_srgb: |sliders, mapping| C::default().as_srgb_mapped(sliders, mapping),
+            in_gamut: |sliders| C::default().in_gamut(sliders),
+            from_srgb: C::from_srgb,
+        }
+    }
+
+    pub fn as_srgb(&self, mapping: GamutMapping) -> Srgb {
+        (self.as_srgb)(&self.sliders, mapping)
+    }
+
+    pub fn in_gamut(&self) -> bool {
+        (self.in_gamut)(&self.sliders)
+    }
+
+    fn reset(&mut self) {
+        self.sliders = (self.init_sliders)();
+    }
+
+    fn set_srgb(&mut self, srgb: Srgb) {
+        self.sliders = (self.from_srgb)(srgb);
+    }
+}
+
+fn all_models() -> Vec<Model> {
+    vec![
+        Model::new::<RGBChooser>("srgb", "sRGB"),
+        Model::new::<HSVChooser>("hsv", "HSV"),
+        Model::new::<HSLChooser>("hsl", "HSL"),
+        Model::new::<HWBChooser>("hwb", "HWB"),
+        Model::new::<LABChooser>("lab", "CIELAB"),
+        Model::new::<XYZChooser>("xyz", "CIEXYZ"),
+        Model::new::<LCHChooser>("lch", "CIELCH"),
+        Model::new::<OklabChooser>("oklab", "Oklab"),
+        Model::new::<OklchChooser>("oklch", "Oklch"),
+        Model::new::<LUVChooser>("luv", "CIELUV"),
+        Model::new::<LCHUVChooser>("lchuv", "CIELCh(uv)"),
+        Model::new::<HSLUVChooser>("hsluv", "HSLuv"),
+    ]
+}
+
+pub struct Settings {
+    pub exclusion: ExclusionPreset,
+    pub metric: Metric,
+    pub delay: Duration,
+    pub gamut_mapping: GamutMapping,
+    /// Colors per round, a single color unless between the sequence bounds.
+    pub colors: usize,
+    pub presentation: Presentation,
+    pub matching: Matching,
+}
+
+impl Settings {
+    fn round(&self, seed: ChallengeSeed) -> Round {
+        let distribution = TargetDistribution::default();
+        if let Some(mut challenge) =
+            SequenceChallenge::from_seed_with(seed, self.colors, distribution, self.exclusion)
+        {
+            challenge.set_metric(self.metric);
+            challenge.set_presentation(self.presentation);
+            return Round::Sequence(SequenceRound::with_delay(
+                challenge,
+                SystemClock::new(),
+                self.delay,
+            ));
+        }
+
+        let mut challenge = ColorChallenge::from_seed_with(seed, distribution, self.exclusion);
+        challenge.set_metric(self.metric);
+        Round::Single(GameRound::with_delay(
+            challenge,
+            SystemClock::new(),
+            self.delay,
+        ))
+    }
+}
+
+/// A round of one color or of several recalled one by one.
+pub enum Round {
+    Single(GameRound<SystemClock>),
+    Sequence(SequenceRound<SystemClock>),
+}
+
+impl Round {
+    pub fn phase(&self) -> GamePhase {
+        match self {
+            Round::Single(round) => round.phase(),
+            Round::Sequence(round) => round.phase(),
+        }
+    }
+
+    pub fn remaining_delay(&self) -> Duration {
+        match self {
+            Round::Single(round) => round.remaining_delay(),
+            Round::Sequence(round) => round.remaining_delay(),
+        }
+    }
+
+    pub fn check_excluded(&self, color: &Srgb) -> Option<ExcludeReason> {
+        match self {
+            Round::Single(round) => round.check_excluded(color),
+            Round::Sequence(round) => round.exclusion().check(color),
+        }
+    }
+
+    fn finish_memorizing(&mut self) -> Result<(), PhaseError> {
+        match self {
+            Round::Single(round) => round.finish_memorizing().map(|_| ()),
+            Round::Sequence(round) => round.finish_memorizing(),
+        }
+    }
+}
+
+pub struct App {
+    pub round: Round,
+    pub models: Vec<Model>,
+    pub current_model: usize,
+    pub selected_slider: usize,
+    pub session: GameSession,
+    pub settings: Settings,
+    pub quit: bool,
+    seeds: Box<dyn Iterator<Item = ChallengeSeed>>,
+}
+
+impl App {
+    /// Play the rounds of [`ChallengeSeed::sequence`], so a whole run can be replayed from its
+    /// first seed.
+    pub fn new(settings: Settings, seed: ChallengeSeed) -> Self {
+        let mut seeds = seed.sequence();
+        let first = seeds.next().unwrap_or(seed);
+        App {
+            round: settings.round(first),
+            models: all_models(),
+            current_model: 0,
+            selected_slider: 0,
+            session: GameSession::new(),
+            settings,
+            quit: false,
+            seeds: Box::new(seeds),
+        }
+    }
+
+    fn start_round(&mut self, seed: ChallengeSeed) {
+        self.round = self.settings.round(seed);
+        self.models.iter_mut().for_each(Model::reset);
+        self.current_model = 0;
+        self.selected_slider = 0;
+    }
+
+    pub fn next_round(&mut self) {
+        if let Some(seed) = self.seeds.next() {
+            self.start_round(seed);
+        }
+    }
+
+    pub fn model(&self) -> &Model {
+        &self.models[self.current_model]
+    }
+
+    /// The color being entered, gamut mapped the same way it is shown and scored.
+    pub fn guess(&self) -> Srgb {
+        self.model().as_srgb(self.settings.gamut_mapping)
+    }
+
+    fn switch_model(&mut self, index: usize) {
+        let srgb = self.guess();
+        self.current_model = index % self.models.len();
+        self.models[self.current_model].set_srgb(srgb);
+        self.selected_slider = 0;
+    }
+
+    fn nudge_slider(&mut self, steps: f32) {
+        let slider = &mut self.models[self.current_model].sliders[self.selected_slider];
+        let step = (slider.max - slider.min) / SLIDER_STEPS;
+        slider.value = (slider.value + step * steps).clamp(slider.min, slider.max);
+    }
+
+    pub fn handle_key(&mut self, key: KeyEvent) {
+        if key.code == KeyCode::Char('q')
+            || key.code == KeyCode::Esc
+            || (key.code == KeyCode::Char('c') && key.modifiers.contains(KeyModifiers::CONTROL))
+        {
+            self.quit = true;
+            return;
+        }
+
+        match self.round.phase() {
+            GamePhase::Memorize => {
+                if matches!(key.code, KeyCode::Enter | KeyCode::Char(' ')) {
+                    let _ = self.round.finish_memorizing();
+                }
+            }
+            GamePhase::Delay => {}
+            GamePhase::Recall => self.handle_recall_key(key),
+            GamePhase::Scored => {
+                if matches!(key.code, KeyCode::Enter | KeyCode::Char('n')) {
+                    self.next_round();
+                }
+            }
+        }
+    }
+
+    fn handle_recall_key(&mut self, key: KeyEvent) {
+        let fast = if key.modifiers.contains(KeyModifiers::SHIFT) {
+            8.0
+        } else {
+            1.0
+        };
+        let slider_count = self.model().sliders.len();
+
+        match key.code {
+            KeyCode::Up | KeyCode::Char('k') => {
+                self.selected_slider = (self.selected_slider + slider_count - 1) % slider_count;
+            }
+            KeyCode::Down | KeyCode::Char('j') => {
+                self.selected_slider = (self.selected_slider + 1) % slider_count;
+            }
+            KeyCode::Left | KeyCode::Char('h') => self.nudge_slider(-fast),
+            KeyCode::Right | KeyCode::Char('l') => self.nudge_slider(fast),
+            KeyCode::Char('H') => self.nudge_slider(-8.0),
+            KeyCode::Char('L') => self.nudge_slider(8.0),
+            KeyCode::Tab => self.switch_model(self.current_model + 1),
+            KeyCode::BackTab => {
+                self.switch_model(self.current_model + self.models.len() - 1);
+            }
+            KeyCode::Char(c @ '1'..='9') => {
+                let index = c as usize - '1' as usize;
+                if index < self.models.len() {
+                    self.switch_model(index);
+                }
+            }
+            KeyCode::Enter => {
+                let guess = self.guess();
+                let key = self.model().key;
+                match &mut self.round {
+                    Round::Single(round) => {
+                        if round.submit(guess).is_ok() {
+                            if let Some(result) = round.result(key) {
+                                self.session.push(result);
+                            }
+                        }
+                    }
+                    Round::Sequence(round) => {
+                        // each color is recalled from the starting sliders again
+                        if round.submit(guess).is_ok() {
+                            self.models.iter_mut().for_each(Model::reset);
+                        }
+                    }
+                }
+            }
+            _ => {}
+        }
+    }
+}
@@ -0,0 +1,143 @@
+use std::io::{self, Write};
+use std::time::Duration;
+
+use clap::Parser;
+use color_recall::exclusion::ExclusionPreset;
+use color_recall::game::ChallengeSeed;
+use color_recall::gamut::GamutMapping;
+use color_recall::metric::Metric;
+use color_recall::phase::DEFAULT_DELAY;
+use color_recall::sequence::{Matching, Presentation, MAX_COLORS};
+use crossterm::event::{self, Event, KeyEventKind};
+use crossterm::{cursor, execute, terminal};
+use rand::rngs::OsRng;
+
+mod app;
+mod render;
+
+use app::{App, Settings};
+
+/// Play color recall in a truecolor terminal.
+#[derive(Parser)]
+#[command(version)]
+struct Args {
+    /// Seed of the first round as 16 hex digits, later rounds follow from it.
+    #[arg(long)]
+    seed: Option<ChallengeSeed>,
+
+    /// Seconds the target stays hidden before recalling.
+    #[arg(long, default_value_t = DEFAULT_DELAY.as_secs_f64())]
+    delay: f64,
+
+    /// Metric used for the score: cie76, cie94_graphic_arts, cie94_textiles, cmc, ciede2000,
+    /// ciede2000_improved, oklab or itp.
+    #[arg(long, default_value = Metric::default().key(), value_parser = parse_metric)]
+    metric: Metric,
+
+    /// Exclusion policy for targets: default, strict, none or display_robust.
+    #[arg(long, default_value = ExclusionPreset::default().key(), value_parser = parse_exclusion)]
+    exclusion: ExclusionPreset,
+
+    /// How out of gamut colors are displayed: clip or reduce_chroma.
+    #[arg(long, default_value = GamutMapping::default().key(), value_parser = parse_gamut_mapping)]
+    gamut_mapping: GamutMapping,
+
+    /// Colors per round, more than one plays palettes recalled one color at a time.
+    #[arg(long, default_value_t = 1, value_parser = clap::value_parser!(u8).range(1..=MAX_COLORS as i64))]
+    colors: u8,
+
+    /// How the colors of a palette are shown while memorizing: together or sequential.
+    #[arg(long, default_value = Presentation::default().key(), value_parser = parse_presentation)]
+    presentation: Presentation,
+
+    /// Pair guesses with the colors they match best instead of by position.
+    #[arg(long)]
+    any_order: bool,
+}
+
+fn parse_metric(key: &str) -> Result<Metric, String> {
+    Metric::from_key(key).ok_or_else(|| format!("unknown metric {:?}", key))
+}
+
+fn parse_exclusion(key: &str) -> Result<ExclusionPreset, String> {
+    ExclusionPreset::from_key(key).ok_or_else(|| format!("unknown exclusion policy {:?}", key))
+}
+
+fn parse_gamut_mapping(key: &str) -> Result<GamutMapping, String> {
+    GamutMapping::from_key(key).ok_or_else(|| format!("unknown gamut mapping {:?}", key))
+}
+
+fn parse_presentation(key: &str) -> Result<Presentation, String> {
+    Presentation::from_key(key).ok_or_else(|| format!("unknown presentation {:?}", key))
+}
+
+fn restore_terminal() -> io::Result<()> {
+    execute!(io::stdout(), terminal::LeaveAlternateScreen, cursor::Show)?;
+    terminal::disable_raw_mode()
+}
+
+fn run(app: &mut App) -> io::Result<()> {
+    let mut stdout = io::stdout();
+    while !app.quit {
+        render::draw(&mut stdout, app)?;
+
+        // redraw at least every 100ms so the countdown moves and the delay ends on its own
+        if event::poll(Duration::from_millis(100))? {
+            if let Event::Key(key) = event::read()? {
+                if key.kind == KeyEventKind::Press {
+                    app.handle_key(key);
+                }
+            }
+        }
+    }
+    stdout.flush()
+}
+
+fn main() -> io::Result<()> {
+    let args = Args::parse();
+    let delay = match Duration::try_from_secs_f64(args.delay) {
+        Ok(delay) => delay,
+        Err(err) => {
+            eprintln!("invalid --delay {:?}: {}", args.delay, err);
+            std::process::exit(2);
+        }
+    };
+
+    let settings = Settings {
+        exclusion: args.exclusion,
+        metric: args.metric,
+        delay,
+        gamut_mapping: args.gamut_mapping,
+        colors: args.colors.into(),
+        presentation: args.presentation,
+        matching: if args.any_order {
+            Matching::BestMatch
+        } else {
+            Matching::Ordered
+        },
+    };
+    let seed = args
+        .seed
+        .unwrap_or_else(|| ChallengeSeed::random(&mut OsRng));
+    let mut app = App::new(settings, seed);
+
+    let default_hook = std::panic::take_hook();
+    std::panic::set_hook(Box::new(move |info| {
+        let _ = restore_terminal();
+        default_hook(info);
+    }));
+
+    terminal::enable_raw_mode()?;
+    execute!(io::stdout(), terminal::EnterAlternateScreen, cursor::Hide)?;
+    let result = run(&mut app);
+    restore_terminal()?;
+    result?;
+
+    if let Some(stats) = app.session.stats() {
+        println!(
+            "{} rounds, mean {:.2}, median {:.2}, best {:.2}, worst {:.2}",
+            stats.count, stats.mean, stats.median, stats.best, stats.worst
+        );
+    }
+    Ok(())
+}
@@ -0,0 +1,337 @@
+use std::io::{self, Write};
+
+use color_recall::game::{format_hex, Slider};
+use color_recall::metric::DifferenceMetric;
+use color_recall::phase::{GamePhase, GameRound, SystemClock};
+use color_recall::sequence::{Presentation, SequenceRound};
+use crossterm::style::{Attribute, Color, Print, ResetColor, SetAttribute, SetBackgroundColor};
+use crossterm::terminal::{Clear, ClearType};
+use crossterm::{cursor::MoveTo, queue};
+use palette::Srgb;
+
+use crate::app::{App, Round};
+
+const SWATCH_WIDTH: u16 = 24;
+// for rows of more than two swatches, so six still fit in a terminal
+const NARROW_SWATCH_WIDTH: u16 = 10;
+const SWATCH_HEIGHT: u16 = 8;
+const SLIDER_WIDTH: usize = 32;
+
+fn to_color(srgb: Srgb) -> Color {
+    let srgb: Srgb<u8> = srgb.into_format();
+    Color::Rgb {
+        r: srgb.red,
+        g: srgb.green,
+        b: srgb.blue,
+    }
+}
+
+/// Writes lines top to bottom, keeping track of the row.
+struct Screen<'a, W: Write> {
+    out: &'a mut W,
+    row: u16,
+}
+
+impl<W: Write> Screen<'_, W> {
+    fn line(&mut self, text: impl std::fmt::Display) -> io::Result<()> {
+        queue!(self.out, MoveTo(0, self.row), Print(text))?;
+        self.row += 1;
+        Ok(())
+    }
+
+    fn title(&mut self, text: &str) -> io::Result<()> {
+        queue!(
+            self.out,
+            MoveTo(0, self.row),
+            SetAttribute(Attribute::Bold),
+            Print(text),
+            SetAttribute(Attribute::Reset)
+        )?;
+        self.row += 2;
+        Ok(())
+    }
+
+    fn blank(&mut self) {
+        self.row += 1;
+    }
+
+    fn swatches(&mut self, swatches: &[(&str, Srgb)]) -> io::Result<()> {
+        let width = if swatches.len() > 2 {
+            NARROW_SWATCH_WIDTH
+        } else {
+            SWATCH_WIDTH
+        };
+        for (i, (label, _)) in swatches.iter().enumerate() {
+            let col = i as u16 * (width + 4);
+            queue!(self.out, MoveTo(col, self.row), Print(label))?;
+        }
+        self.row += 1;
+
+        let fill = " ".repeat(width as usize);
+        for _ in 0..SWATCH_HEIGHT {
+            for (i, (_, color)) in swatches.iter().enumerate() {
+                let col = i as u16 * (width + 4);
+                queue!(
+                    self.out,
+                    MoveTo(col, self.row),
+                    SetBackgroundColor(to_color(*color)),
+                    Print(&fill),
+                    ResetColor
+                )?;
+            }
+            self.row += 1;
+        }
+        self.blank();
+        Ok(())
+    }
+
+    fn slider(&mut self, slider: &Slider<f32>, selected: bool) -> io::Result<()> {
+        let fraction = ((slider.value - slider.min) / (slider.max - slider.min)).clamp(0.0, 1.0);
+        let filled = (fraction * SLIDER_WIDTH as f32).round() as usize;
+        self.line(format!(
+            "{} {:<4} [{}{}] {:.3}",
+            if selected { ">" } else { " " },
+            slider.name,
+            "=".repeat(filled),
+            "-".repeat(SLIDER_WIDTH - filled),
+            slider.value
+        ))
+    }
+}
+
+fn judge(value: f32, below: &'static str, above: &'static str, ok: &'static str) -> &'static str {
+    if value < -1.0 {
+        below
+    } else if value > 1.0 {
+        above
+    } else {
+        ok
+    }
+}
+
+fn numbered_swatches<W: Write>(
+    screen: &mut Screen<'_, W>,
+    label: &str,
+    first: usize,
+    colors: &[Srgb],
+) -> io::Result<()> {
+    let labels: Vec<String> = (first + 1..=first + colors.len())
+        .map(|n| format!("{} {}", label, n))
+        .collect();
+    let swatches: Vec<(&str, Srgb)> = labels
+        .iter()
+        .map(String::as_str)
+        .zip(colors.iter().copied())
+        .collect();
+    screen.swatches(&swatches)
+}
+
+fn draw_score<W: Write>(
+    screen: &mut Screen<'_, W>,
+    round: &GameRound<SystemClock>,
+) -> io::Result<()> {
+    let (Ok(challenge), Some(guess)) = (round.challenge(), round.guess()) else {
+        return Ok(());
+    };
+    let target = *challenge.target_color();
+    screen.swatches(&[("Target", target), ("Your color", guess)])?;
+    screen.line(format!(
+        "Target {}, yours {}",
+        format_hex(target),
+        format_hex(guess)
+    ))?;
+    screen.line(format!("Seed {}", challenge.seed()))?;
+    screen.blank();
+
+    screen.line(format!(
+        "{}: {:.2} (lower is better)",
+        challenge.metric().name(),
+        challenge.compute_distance(guess)
+    ))?;
+    let breakdown = challenge.compute_breakdown(guess);
+    screen.line(judge(
+        breakdown.weighted_lightness(),
+        "Too dark.",
+        "Too light.",
+        "Lightness was right.",
+    ))?;
+    screen.line(judge(
+        breakdown.weighted_chroma(),
+        "Too dull.",
+        "Too vivid.",
+        "Colorfulness was right.",
+    ))?;
+    screen.line(judge(
+        breakdown.weighted_hue(),
+        "Hue was off.",
+        "Hue was off.",
+        "Hue was right.",
+    ))?;
+    screen.line(format!(
+        "CIEDE2000 breakdown: ΔE00 {:.2}, ΔL' {:.2}, ΔC' {:.2}, ΔH' {:.2}",
+        breakdown.delta_e, breakdown.delta_l, breakdown.delta_c, breakdown.delta_h
+    ))?;
+    screen.blank();
+
+    for (metric, value) in challenge.compute_all_distances(guess) {
+        screen.line(format!("  {:<32} {:.2}", metric.name(), value))?;
+    }
+    screen.blank();
+    Ok(())
+}
+
+fn draw_sequence_score<W: Write>(
+    screen: &mut Screen<'_, W>,
+    app: &App,
+    round: &SequenceRound<SystemClock>,
+) -> io::Result<()> {
+    let (Ok(challenge), Some(score)) = (round.challenge(), round.score(app.settings.matching))
+    else {
+        return Ok(());
+    };
+    // guesses in the order of the targets they were paired with
+    let paired: Vec<Srgb> = score
+        .assignment
+        .iter()
+        .map(|guess| round.guesses()[*guess])
+        .collect();
+    numbered_swatches(screen, "Target", 0, challenge.targets())?;
+    numbered_swatches(screen, "Yours", 0, &paired)?;
+
+    for (i, ((target, guess), distance)) in challenge
+        .targets()
+        .iter()
+        .zip(&paired)
+        .zip(&score.distances)
+        .enumerate()
+    {
+        screen.line(format!(
+            "  {} target {}, yours {} (guess {}): {:.2}",
+            i + 1,
+            format_hex(*target),
+            format_hex(*guess),
+            score.assignment[i] + 1,
+            distance
+        ))?;
+    }
+    screen.line(format!("Seed {}", challenge.seed()))?;
+    screen.blank();
+
+    screen.line(format!(
+        "{}: mean {:.2}, total {:.2} (lower is better)",
+        challenge.metric().name(),
+        score.mean(),
+        score.total()
+    ))?;
+    screen.blank();
+    Ok(())
+}
+
+pub fn draw<W: Write>(out: &mut W, app: &App) -> io::Result<()> {
+    queue!(out, Clear(ClearType::All))?;
+    let mut screen = Screen { out, row: 0 };
+
+    match app.round.phase() {
+        GamePhase::Memorize => match &app.round {
+            Round::Single(round) => {
+                screen.title("Step 1: Memorize the color")?;
+                if let Ok(target) = round.target() {
+                    screen.swatches(&[("Target", *target)])?;
+                }
+                screen.line("Press Enter when you have memorized it, q to quit.")?;
+            }
+            Round::Sequence(round) => {
+                let Ok((first, targets)) = round.shown() else {
+                    return Ok(());
+                };
+                if round.presentation() == Presentation::Sequential {
+                    screen.title(&format!(
+                        "Step 1: Memorize color {} of {}",
+                        first + 1,
+                        round.len()
+                    ))?;
+                } else {
+                    screen.title("Step 1: Memorize the colors")?;
+                }
+                numbered_swatches(&mut screen, "Target", first, targets)?;
+                if first + targets.len() < round.len() {
+                    screen.line("Press Enter for the next color, q to quit.")?;
+                } else {
+                    screen.line("Press Enter when you have memorized them, q to quit.")?;
+                }
+            }
+        },
+        GamePhase::Delay => {
+            screen.title("Step 2: Wait")?;
+            screen.line(format!("{:.1}s", app.round.remaining_delay().as_secs_f32()))?;
+        }
+        GamePhase::Recall => {
+            match &app.round {
+                Round::Single(_) => screen.title("Step 3: Recall the color")?,
+                Round::Sequence(round) => screen.title(&format!(
+                    "Step 3: Recall color {} of {}",
+                    round.recalling() + 1,
+                    round.len()
+                ))?,
+            }
+            let model = app.model();
+            screen.swatches(&[("Your color", app.guess())])?;
+
+            let names: Vec<String> = app
+                .models
+                .iter()
+                .enumerate()
+                .map(|(i, m)| {
+                    // only the first nine have a number key
+                    let label = if i < 9 {
+                        format!("{}:{}", i + 1, m.name)
+                    } else {
+                        m.name.to_string()
+                    };
+                    if i == app.current_model {
+                        format!("[{}]", label)
+                    } else {
+                        format!(" {} ", label)
+                    }
+                })
+                .collect();
+            screen.line(names.join(" "))?;
+            screen.blank();
+
+            for (i, slider) in model.sliders.iter().enumerate() {
+                screen.slider(slider, i == app.selected_slider)?;
+            }
+            screen.blank();
+
+            if !model.in_gamut() {
+                screen.line("Out of the sRGB gamut, the closest displayable color is shown.")?;
+            }
+            if let Some(reason) = app.round.check_excluded(&app.guess()) {
+                screen.line(format!(
+                    "{}: targets are never picked from colors like this.",
+                    reason
+                ))?;
+            }
+            screen.line(
+                "Up/Down select, Left/Right adjust (Shift for more), Tab or 1-9 switch model, Enter submit.",
+            )?;
+        }
+        GamePhase::Scored => {
+            screen.title("Step 4: Score")?;
+            match &app.round {
+                Round::Single(round) => draw_score(&mut screen, round)?,
+                Round::Sequence(round) => draw_sequence_score(&mut screen, app, round)?,
+            }
+
+            if let Some(stats) = app.session.stats() {
+                screen.line(format!(
+                    "Session ({} rounds): mean {:.2}, median {:.2}, best {:.2}, worst {:.2}, σ {:.2}",
+                    stats.count, stats.mean, stats.median, stats.best, stats.worst, stats.std_dev
+                ))?;
+            }
+            screen.line("Press Enter for the next round, q to quit.")?;
+        }
+    }
+
+    screen.out.flush()
+}
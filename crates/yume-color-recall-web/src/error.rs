@@ -0,0 +1,137 @@
+use std::fmt::Display;
+
+use color_recall::{
+    css::CssColorError, phase::PhaseError, practice::PaletteError, recognition::RecognitionError,
+};
+
+/// Misuse of the bindings, surfaced to JS as a thrown `Error` with this message.
+#[derive(Debug, Clone, PartialEq)]
+pub enum GameError {
+    UnknownModel(String),
+    UnknownMetric(String),
+    UnknownExclusionPolicy(String),
+    UnknownGamutMapping(String),
+    UnknownDeficiency(String),
+    UnknownCvdMode(String),
+    UnknownCssFormat(String),
+    BadSliderCount {
+        model: String,
+        expected: usize,
+        got: usize,
+    },
+    NonFiniteValue {
+        what: String,
+        index: usize,
+    },
+    OutOfRange {
+        what: String,
+        index: usize,
+    },
+    InvalidHex(String),
+    InvalidColor(CssColorError),
+    InvalidPalette(PaletteError),
+    WrongPhase(PhaseError),
+    NoRecognitionRound,
+    NoDistractors {
+        delta_e: f32,
+    },
+    RecognitionAlreadyDrawn {
+        alternatives: usize,
+        delta_e: f32,
+    },
+    InvalidChoice {
+        choice: usize,
+        options: usize,
+    },
+    NoDailyRound {
+        round: usize,
+        rounds: usize,
+    },
+}
+
+impl Display for GameError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            GameError::UnknownModel(model) => write!(f, "unknown color model {:?}", model),
+            GameError::UnknownMetric(metric) => write!(f, "unknown metric {:?}", metric),
+            GameError::UnknownExclusionPolicy(policy) => {
+                write!(f, "unknown exclusion policy {:?}", policy)
+            }
+            GameError::UnknownGamutMapping(mapping) => {
+                write!(f, "unknown gamut mapping {:?}", mapping)
+            }
+            GameError::UnknownDeficiency(deficiency) => {
+                write!(f, "unknown color vision deficiency {:?}", deficiency)
+            }
+            GameError::UnknownCvdMode(mode) => write!(f, "unknown simulation mode {:?}", mode),
+            GameError::UnknownCssFormat(format) => write!(f, "unknown CSS format {:?}", format),
+            GameError::BadSliderCount {
+                model,
+                expected,
+                got,
+            } => write!(
+                f,
+                "model {:?} has {} sliders but {} values were given",
+                model, expected, got
+            ),
+            GameError::NonFiniteValue { what, index } => {
+                write!(f, "value {} of {} is not a finite number", index, what)
+            }
+            GameError::OutOfRange { what, index } => {
+                write!(f, "value {} of {} is out of range", index, what)
+            }
+            GameError::InvalidHex(hex) => {
+                write!(f, "expected #rgb or #rrggbb, got {:?}", hex)
+            }
+            GameError::InvalidColor(err) => err.fmt(f),
+            GameError::InvalidPalette(err) => err.fmt(f),
+            GameError::WrongPhase(err) => err.fmt(f),
+            GameError::NoRecognitionRound => write!(f, "no recognition options were drawn"),
+            GameError::NoDistractors { delta_e } => {
+                write!(f, "no distractors found at ΔE {}", delta_e)
+            }
+            GameError::RecognitionAlreadyDrawn {
+                alternatives,
+                delta_e,
+            } => write!(
+                f,
+                "recognition options were already drawn as {} alternatives at ΔE {}",
+                alternatives, delta_e
+            ),
+            GameError::InvalidChoice { choice, options } => write!(
+                f,
+                "choice {} is out of range for {} options",
+                choice, options
+            ),
+            GameError::NoDailyRound { round, rounds } => write!(
+                f,
+                "the daily challenge has {} rounds, there is no round {}",
+                rounds, round
+            ),
+        }
+    }
+}
+
+impl std::error::Error for GameError {}
+
+impl From<PhaseError> for GameError {
+    fn from(err: PhaseError) -> Self {
+        GameError::WrongPhase(err)
+    }
+}
+
+impl From<RecognitionError> for GameError {
+    fn from(err: RecognitionError) -> Self {
+        match err {
+            RecognitionError::WrongPhase(err) => GameError::WrongPhase(err),
+            RecognitionError::NoDistractors { delta_e } => GameError::NoDistractors { delta_e },
+            RecognitionError::AlreadyDrawn {
+                alternatives,
+                delta_e,
+            } => GameError::RecognitionAlreadyDrawn {
+                alternatives,
+                delta_e,
+            },
+        }
+    }
+}
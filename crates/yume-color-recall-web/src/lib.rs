@@ -1,13 +1,30 @@
-use std::{backtrace, sync::RwLock};
+use std::{backtrace, time::Duration};
 
+use color_recall::adaptive::AdaptiveDelay;
+use color_recall::css::{parse_css_color, to_css, CssFormat};
+use color_recall::cvd::{CvdMode, CvdSimulation, Deficiency};
+use color_recall::daily::{daily_seed, UtcDate, DAILY_CHALLENGE_ROUNDS};
+use color_recall::exclusion::{ExclusionPolicy, ExclusionPreset};
 use color_recall::game::{
-    chooser_convert, ColorChallenge, ColorChooser, ExcludeReason, HSLChooser, HSVChooser,
-    LABChooser, LCHChooser, RGBChooser, Slider, XYZChooser,
+    chooser_convert_mapped, ChallengeSeed, ColorChallenge, ColorChooser, ExcludeReason, HSLChooser,
+    HSLUVChooser, HSVChooser, HWBChooser, LABChooser, LCHChooser, LCHUVChooser, LUVChooser,
+    OklabChooser, OklchChooser, RGB8Chooser, RGBChooser, Slider, XYZChooser,
 };
+use color_recall::gamut::{reduce_chroma, GamutMapping};
+use color_recall::metric::{Ciede2000Breakdown, DifferenceMetric, Metric};
+use color_recall::phase::{Clock, GamePhase, GameRound, PhaseError};
+use color_recall::practice::{PaletteEntry, PracticePalette};
+use color_recall::recognition::{RecognitionStats, RecognitionTrial};
+use color_recall::sampling::TargetDistribution;
+use color_recall::session::{GameSession, RoundResult, SessionStats};
 use palette::{Darken, Lighten, Srgb};
 use rand::rngs::OsRng;
 use wasm_bindgen::prelude::*;
 
+mod error;
+
+pub use error::GameError;
+
 #[wasm_bindgen]
 extern "C" {
     #[wasm_bindgen(js_namespace = console)]
@@ -17,26 +34,49 @@ extern "C" {
 
     #[wasm_bindgen(js_namespace = console)]
     fn error(s: &str);
+
+    #[wasm_bindgen(js_namespace = performance, js_name = now)]
+    fn performance_now() -> f64;
+}
+
+/// Milliseconds from `performance.now()`, the clock rounds are timed with.
+///
+/// Unlike `Date.now()` it is monotonic, so changing the system clock does not affect rounds.
+pub struct JsClock;
+
+impl Clock for JsClock {
+    fn now(&self) -> Duration {
+        Duration::from_secs_f64(performance_now().max(0.0) / 1000.0)
+    }
 }
 
 pub struct GameContext {
-    game: ColorChallenge,
+    round: GameRound<JsClock>,
 
     slider_srgb: (RGBChooser, Box<[Slider<f32>]>),
+    /// Also backs the `"hex"` model, which edits the same color as text.
+    slider_rgb8: (RGB8Chooser, Box<[Slider<u8>]>),
     slider_hsv: (HSVChooser, Box<[Slider<f32>]>),
     slider_hsl: (HSLChooser, Box<[Slider<f32>]>),
+    slider_hwb: (HWBChooser, Box<[Slider<f32>]>),
     slider_lab: (LABChooser, Box<[Slider<f32>]>),
     slider_xyz: (XYZChooser, Box<[Slider<f32>]>),
     slider_lch: (LCHChooser, Box<[Slider<f32>]>),
+    slider_oklab: (OklabChooser, Box<[Slider<f32>]>),
+    slider_oklch: (OklchChooser, Box<[Slider<f32>]>),
+    slider_luv: (LUVChooser, Box<[Slider<f32>]>),
+    slider_lchuv: (LCHUVChooser, Box<[Slider<f32>]>),
+    slider_hsluv: (HSLUVChooser, Box<[Slider<f32>]>),
+
+    gamut_mapping: GamutMapping,
+    /// Exclusion policy of the rounds started from now on.
+    exclusion: ExclusionPreset,
+    /// Simulation applied to every color shown, see [`CvdMode::Display`].
+    display_simulation: Option<CvdSimulation>,
 }
 
 fn srgb_to_css(input: &Srgb) -> String {
-    format!(
-        "rgb({}, {}, {})",
-        (input.red * 255.0) as u8,
-        (input.green * 255.0) as u8,
-        (input.blue * 255.0) as u8
-    )
+    to_css(*input, CssFormat::Rgb)
 }
 
 pub struct JSSliderInfo {
@@ -44,6 +84,8 @@ pub struct JSSliderInfo {
     pub min: f32,
     pub max: f32,
     pub value: f32,
+    /// Only whole numbers are accepted.
+    pub integer: bool,
 }
 
 impl From<Slider<f32>> for JSSliderInfo {
@@ -53,39 +95,159 @@ impl From<Slider<f32>> for JSSliderInfo {
             min: slider.min,
             max: slider.max,
             value: slider.value,
+            integer: false,
+        }
+    }
+}
+
+impl From<Slider<u8>> for JSSliderInfo {
+    fn from(slider: Slider<u8>) -> Self {
+        Self {
+            name: slider.name,
+            min: slider.min.into(),
+            max: slider.max.into(),
+            value: slider.value.into(),
+            integer: true,
         }
     }
 }
 
-impl Into<JsValue> for JSSliderInfo {
-    fn into(self) -> JsValue {
+impl From<JSSliderInfo> for JsValue {
+    fn from(val: JSSliderInfo) -> Self {
         let obj = js_sys::Object::new();
 
         js_sys::Reflect::set(
             &obj,
             &JsValue::from_str("name"),
-            &JsValue::from_str(self.name),
+            &JsValue::from_str(val.name),
         )
         .unwrap();
 
         js_sys::Reflect::set(
             &obj,
             &JsValue::from_str("min"),
-            &JsValue::from_f64(self.min as f64),
+            &JsValue::from_f64(val.min as f64),
         )
         .unwrap();
 
         js_sys::Reflect::set(
             &obj,
             &JsValue::from_str("max"),
-            &JsValue::from_f64(self.max as f64),
+            &JsValue::from_f64(val.max as f64),
+        )
+        .unwrap();
+
+        js_sys::Reflect::set(
+            &obj,
+            &JsValue::from_str("value"),
+            &JsValue::from_f64(val.value as f64),
+        )
+        .unwrap();
+
+        js_sys::Reflect::set(
+            &obj,
+            &JsValue::from_str("integer"),
+            &JsValue::from_bool(val.integer),
+        )
+        .unwrap();
+
+        obj.into()
+    }
+}
+
+pub struct JSScoreInfo {
+    pub metric: &'static str,
+    pub name: &'static str,
+    pub value: f32,
+}
+
+impl From<(Metric, f32)> for JSScoreInfo {
+    fn from((metric, value): (Metric, f32)) -> Self {
+        Self {
+            metric: metric.key(),
+            name: metric.name(),
+            value,
+        }
+    }
+}
+
+pub struct JSPaletteEntry {
+    pub name: String,
+    pub css: String,
+    /// Why the current exclusion policy would never draw this color as a random target.
+    pub excluded: Option<&'static str>,
+}
+
+impl JSPaletteEntry {
+    fn new(entry: &PaletteEntry, exclusion: &ExclusionPreset) -> Self {
+        Self {
+            name: entry.name.clone(),
+            css: srgb_to_css(&entry.color),
+            excluded: exclusion.check(&entry.color).map(exclude_reason_key),
+        }
+    }
+}
+
+impl From<JSPaletteEntry> for JsValue {
+    fn from(val: JSPaletteEntry) -> Self {
+        let obj = js_sys::Object::new();
+
+        js_sys::Reflect::set(
+            &obj,
+            &JsValue::from_str("name"),
+            &JsValue::from_str(&val.name),
+        )
+        .unwrap();
+
+        js_sys::Reflect::set(
+            &obj,
+            &JsValue::from_str("css"),
+            &JsValue::from_str(&val.css),
+        )
+        .unwrap();
+
+        js_sys::Reflect::set(
+            &obj,
+            &JsValue::from_str("excluded"),
+            &val.excluded.map_or(JsValue::UNDEFINED, JsValue::from_str),
+        )
+        .unwrap();
+
+        obj.into()
+    }
+}
+
+fn exclude_reason_key(reason: ExcludeReason) -> &'static str {
+    match reason {
+        ExcludeReason::LowSaturation => "low_saturation",
+        ExcludeReason::HighSaturation => "high_saturation",
+        ExcludeReason::TooBright => "too_bright",
+        ExcludeReason::TooDark => "too_dark",
+    }
+}
+
+impl From<JSScoreInfo> for JsValue {
+    fn from(val: JSScoreInfo) -> Self {
+        let obj = js_sys::Object::new();
+
+        js_sys::Reflect::set(
+            &obj,
+            &JsValue::from_str("metric"),
+            &JsValue::from_str(val.metric),
+        )
+        .unwrap();
+
+        js_sys::Reflect::set(
+            &obj,
+            &JsValue::from_str("name"),
+            &JsValue::from_str(val.name),
         )
         .unwrap();
 
         js_sys::Reflect::set(
             &obj,
             &JsValue::from_str("value"),
-            &JsValue::from_f64(self.value as f64),
+            &JsValue::from_f64(val.value as f64),
         )
         .unwrap();
 
@@ -93,104 +255,367 @@ impl Into<JsValue> for JSSliderInfo {
     }
 }
 
+pub struct JSScoreBreakdown(pub Ciede2000Breakdown);
+
+impl From<JSScoreBreakdown> for JsValue {
+    fn from(val: JSScoreBreakdown) -> Self {
+        let obj = js_sys::Object::new();
+        let b = val.0;
+
+        for (key, value) in [
+            ("delta_l", b.delta_l),
+            ("delta_c", b.delta_c),
+            ("delta_h", b.delta_h),
+            ("weighted_l", b.weighted_lightness()),
+            ("weighted_c", b.weighted_chroma()),
+            ("weighted_h", b.weighted_hue()),
+            ("s_l", b.s_l),
+            ("s_c", b.s_c),
+            ("s_h", b.s_h),
+            ("r_t", b.r_t),
+            ("delta_e", b.delta_e),
+        ] {
+            js_sys::Reflect::set(
+                &obj,
+                &JsValue::from_str(key),
+                &JsValue::from_f64(value as f64),
+            )
+            .unwrap();
+        }
+
+        obj.into()
+    }
+}
+
+pub struct JSRecognitionStats(pub RecognitionStats);
+
+impl From<JSRecognitionStats> for JsValue {
+    fn from(val: JSRecognitionStats) -> Self {
+        let obj = js_sys::Object::new();
+        let stats = val.0;
+
+        let levels = js_sys::Array::new();
+        for level in &stats.levels {
+            let entry = js_sys::Object::new();
+            for (key, value) in [
+                ("delta_e", level.delta_e as f64),
+                ("trials", level.trials as f64),
+                ("correct", level.correct as f64),
+                ("accuracy", level.accuracy() as f64),
+            ] {
+                js_sys::Reflect::set(&entry, &JsValue::from_str(key), &JsValue::from_f64(value))
+                    .unwrap();
+            }
+            levels.push(&entry);
+        }
+        js_sys::Reflect::set(&obj, &JsValue::from_str("levels"), &levels).unwrap();
+
+        js_sys::Reflect::set(
+            &obj,
+            &JsValue::from_str("chance"),
+            &JsValue::from_f64(stats.chance as f64),
+        )
+        .unwrap();
+
+        js_sys::Reflect::set(
+            &obj,
+            &JsValue::from_str("threshold"),
+            &stats
+                .threshold()
+                .map(|t| JsValue::from_f64(t as f64))
+                .unwrap_or(JsValue::NULL),
+        )
+        .unwrap();
+
+        obj.into()
+    }
+}
+
+pub struct JSSessionStats(pub SessionStats);
+
+impl From<JSSessionStats> for JsValue {
+    fn from(val: JSSessionStats) -> Self {
+        let obj = js_sys::Object::new();
+        let stats = val.0;
+
+        for (key, value) in [
+            ("count", stats.count as f64),
+            ("mean", stats.mean as f64),
+            ("median", stats.median as f64),
+            ("best", stats.best as f64),
+            ("worst", stats.worst as f64),
+            ("std_dev", stats.std_dev as f64),
+        ] {
+            js_sys::Reflect::set(&obj, &JsValue::from_str(key), &JsValue::from_f64(value)).unwrap();
+        }
+
+        obj.into()
+    }
+}
+
+impl Default for GameContext {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 impl GameContext {
     pub fn new() -> Self {
-        let srgb_chooser = RGBChooser::default();
+        Self::with_challenge(ColorChallenge::new(&mut OsRng))
+    }
+
+    pub fn with_challenge(game: ColorChallenge) -> Self {
+        Self::with_round(GameRound::new(game, JsClock))
+    }
+
+    pub fn with_round(round: GameRound<JsClock>) -> Self {
+        let srgb_chooser = RGBChooser;
         let srgb_sliders = srgb_chooser.init_sliders();
         Self {
-            game: ColorChallenge::new(&mut OsRng::default()),
+            round,
             slider_srgb: (srgb_chooser, srgb_sliders),
-            slider_hsv: (HSVChooser::default(), HSVChooser::default().init_sliders()),
-            slider_hsl: (HSLChooser::default(), HSLChooser::default().init_sliders()),
-            slider_lab: (LABChooser::default(), LABChooser::default().init_sliders()),
-            slider_xyz: (XYZChooser::default(), XYZChooser::default().init_sliders()),
-            slider_lch: (LCHChooser::default(), LCHChooser::default().init_sliders()),
+            slider_rgb8: (RGB8Chooser, RGB8Chooser.init_sliders()),
+            slider_hsv: (HSVChooser, HSVChooser.init_sliders()),
+            slider_hsl: (HSLChooser, HSLChooser.init_sliders()),
+            slider_hwb: (HWBChooser, HWBChooser.init_sliders()),
+            slider_lab: (LABChooser, LABChooser.init_sliders()),
+            slider_xyz: (XYZChooser, XYZChooser.init_sliders()),
+            slider_lch: (LCHChooser, LCHChooser.init_sliders()),
+            slider_oklab: (OklabChooser, OklabChooser.init_sliders()),
+            slider_oklch: (OklchChooser, OklchChooser.init_sliders()),
+            slider_luv: (LUVChooser, LUVChooser.init_sliders()),
+            slider_lchuv: (LCHUVChooser, LCHUVChooser.init_sliders()),
+            slider_hsluv: (HSLUVChooser, HSLUVChooser.init_sliders()),
+            gamut_mapping: GamutMapping::default(),
+            exclusion: ExclusionPreset::default(),
+            display_simulation: None,
+        }
+    }
+
+    /// Select the exclusion policy of the following rounds.
+    ///
+    /// The current target is kept, regenerating it would swap the color the player may already be
+    /// memorizing.
+    pub fn set_exclusion_policy(&mut self, policy: &str) -> Result<(), GameError> {
+        self.exclusion = ExclusionPreset::from_key(policy)
+            .ok_or_else(|| GameError::UnknownExclusionPolicy(policy.to_string()))?;
+        Ok(())
+    }
+
+    pub fn exclusion_policy(&self) -> String {
+        self.exclusion.key().to_string()
+    }
+
+    pub fn challenge_seed(&self) -> String {
+        self.round.seed().to_string()
+    }
+
+    /// `color` as it is shown to the player.
+    fn shown(&self, color: Srgb) -> Srgb {
+        match self.display_simulation {
+            Some(simulation) => simulation.simulate(color),
+            None => color,
         }
     }
 
-    pub fn target_color_css(&self) -> String {
-        srgb_to_css(&self.game.target_color())
+    fn shown_css(&self, color: Srgb) -> String {
+        srgb_to_css(&self.shown(color))
+    }
+
+    pub fn target_color_css(&self) -> Result<String, GameError> {
+        Ok(self.shown_css(*self.round.target()?))
     }
 
-    pub fn current_color_css(&self, model: &str) -> String {
-        let current_color = match model {
-            "srgb" => self.slider_srgb.0.as_srgb(&self.slider_srgb.1),
-            "hsv" => self.slider_hsv.0.as_srgb(&self.slider_hsv.1),
-            "hsl" => self.slider_hsl.0.as_srgb(&self.slider_hsl.1),
-            "lab" => self.slider_lab.0.as_srgb(&self.slider_lab.1),
-            "xyz" => self.slider_xyz.0.as_srgb(&self.slider_xyz.1),
-            "lch" => self.slider_lch.0.as_srgb(&self.slider_lch.1),
-            _ => Srgb::new(0.0, 0.0, 0.0),
+    /// Simulate a color vision deficiency in `mode`, or turn simulation off if `deficiency` is
+    /// `None`.
+    pub fn set_cvd(
+        &mut self,
+        deficiency: Option<&str>,
+        severity: f32,
+        mode: &str,
+    ) -> Result<(), GameError> {
+        let mode =
+            CvdMode::from_key(mode).ok_or_else(|| GameError::UnknownCvdMode(mode.to_string()))?;
+        let simulation = deficiency
+            .map(|key| {
+                Deficiency::from_key(key)
+                    .map(|d| CvdSimulation::new(d, severity))
+                    .ok_or_else(|| GameError::UnknownDeficiency(key.to_string()))
+            })
+            .transpose()?;
+
+        let (display, scoring) = match mode {
+            CvdMode::Display => (simulation, None),
+            CvdMode::Scoring => (None, simulation),
         };
+        self.display_simulation = display;
+        self.round.set_simulation(scoring);
+        Ok(())
+    }
 
-        srgb_to_css(&current_color)
+    fn cvd(&self) -> Option<(CvdSimulation, CvdMode)> {
+        match (self.display_simulation, self.round.simulation()) {
+            (Some(simulation), _) => Some((simulation, CvdMode::Display)),
+            (None, Some(simulation)) => Some((simulation, CvdMode::Scoring)),
+            (None, None) => None,
+        }
+    }
+
+    fn model_color(&self, model: &str) -> Result<(Srgb, bool), GameError> {
+        macro_rules! mapped {
+            ($slider:ident) => {
+                Ok((
+                    self.$slider
+                        .0
+                        .as_srgb_mapped(&self.$slider.1, self.gamut_mapping),
+                    self.$slider.0.in_gamut(&self.$slider.1),
+                ))
+            };
+        }
+        match model {
+            "srgb" => mapped!(slider_srgb),
+            "rgb8" | "hex" => Ok((self.slider_rgb8.0.as_srgb(&self.slider_rgb8.1), true)),
+            "hsv" => mapped!(slider_hsv),
+            "hsl" => mapped!(slider_hsl),
+            "hwb" => mapped!(slider_hwb),
+            "lab" => mapped!(slider_lab),
+            "xyz" => mapped!(slider_xyz),
+            "lch" => mapped!(slider_lch),
+            "oklab" => mapped!(slider_oklab),
+            "oklch" => mapped!(slider_oklch),
+            "luv" => mapped!(slider_luv),
+            "lchuv" => mapped!(slider_lchuv),
+            "hsluv" => mapped!(slider_hsluv),
+            _ => Err(GameError::UnknownModel(model.to_string())),
+        }
+    }
+
+    /// The color shown for `model`, gamut mapped the same way the score sees it.
+    pub fn current_color_css(&self, model: &str) -> Result<String, GameError> {
+        let (current_color, _) = self.model_color(model)?;
+
+        Ok(self.shown_css(current_color))
+    }
+
+    pub fn current_color_in_gamut(&self, model: &str) -> Result<bool, GameError> {
+        let (_, in_gamut) = self.model_color(model)?;
+
+        Ok(in_gamut)
+    }
+
+    pub fn set_gamut_mapping(&mut self, mapping: &str) -> Result<(), GameError> {
+        self.gamut_mapping = GamutMapping::from_key(mapping)
+            .ok_or_else(|| GameError::UnknownGamutMapping(mapping.to_string()))?;
+        Ok(())
+    }
+
+    fn current_srgb(&self) -> Srgb {
+        self.slider_srgb
+            .0
+            .as_srgb_mapped(&self.slider_srgb.1, self.gamut_mapping)
     }
 
     pub fn available_models(&self) -> Vec<String> {
-        ["srgb", "hsv", "hsl", "lab", "xyz", "lch"]
-            .iter()
-            .map(|s| s.to_string())
-            .collect()
+        [
+            "srgb", "rgb8", "hex", "hsv", "hsl", "hwb", "lab", "xyz", "lch", "oklab", "oklch",
+            "luv", "lchuv", "hsluv",
+        ]
+        .iter()
+        .map(|s| s.to_string())
+        .collect()
     }
 
-    pub fn model_name(&self, model: &str) -> String {
-        match model {
+    pub fn model_name(&self, model: &str) -> Result<String, GameError> {
+        let name = match model {
             "srgb" => "sRGB",
+            "rgb8" => "RGB (0-255)",
+            "hex" => "Hex",
             "hsv" => "HSV",
             "hsl" => "HSL",
+            "hwb" => "HWB",
             "lab" => "CIELAB",
             "xyz" => "CIEXYZ",
             "lch" => "CIELCH",
-            _ => "Unknown",
-        }
-        .to_string()
+            "oklab" => "Oklab",
+            "oklch" => "Oklch",
+            "luv" => "CIELUV",
+            "lchuv" => "CIELCh(uv)",
+            "hsluv" => "HSLuv",
+            _ => return Err(GameError::UnknownModel(model.to_string())),
+        };
+
+        Ok(name.to_string())
     }
 
-    pub fn model_info_link(&self, model: &str) -> String {
-        match model {
+    pub fn model_info_link(&self, model: &str) -> Result<String, GameError> {
+        let link = match model {
             "srgb" => "https://en.wikipedia.org/wiki/SRGB",
             "rgb8" => "https://en.wikipedia.org/wiki/RGB_color_model",
+            "hex" => "https://developer.mozilla.org/en-US/docs/Web/CSS/hex-color",
             "hsv" => "https://en.wikipedia.org/wiki/HSL_and_HSV",
             "hsl" => "https://en.wikipedia.org/wiki/HSL_and_HSV",
+            "hwb" => "https://en.wikipedia.org/wiki/HWB_color_model",
             "lab" => "https://en.wikipedia.org/wiki/CIELAB_color_space",
             "xyz" => "https://en.wikipedia.org/wiki/CIE_1931_color_space",
             "lch" => "https://en.wikipedia.org/wiki/CIELAB_color_space#Cylindrical_representation:_CIELCh_or_CIEHLC",
-            _ => "",
-        }
-        .to_string()
+            "oklab" => "https://bottosson.github.io/posts/oklab/",
+            "oklch" => "https://bottosson.github.io/posts/oklab/",
+            "luv" => "https://en.wikipedia.org/wiki/CIELUV",
+            "lchuv" => "https://en.wikipedia.org/wiki/CIELUV#Cylindrical_representation_(CIELCh)",
+            "hsluv" => "https://www.hsluv.org/",
+            _ => return Err(GameError::UnknownModel(model.to_string())),
+        };
+
+        Ok(link.to_string())
     }
 
-    pub fn model_sliders(&self, model: &str) -> Option<Vec<JsValue>> {
+    pub fn model_sliders(&self, model: &str) -> Result<Vec<JsValue>, GameError> {
         macro_rules! impl_model {
             ($name:literal, $slider:ident) => {
                 if model == $name {
-                    return Some(
-                        self.$slider
-                            .1
-                            .iter()
-                            .map(|s| {
-                                let info: JSSliderInfo = s.clone().into();
-
-                                info.into()
-                            })
-                            .collect(),
-                    );
+                    return Ok(self
+                        .$slider
+                        .1
+                        .iter()
+                        .map(|s| {
+                            let info: JSSliderInfo = s.clone().into();
+
+                            info.into()
+                        })
+                        .collect());
                 }
             };
         }
         impl_model!("srgb", slider_srgb);
+        impl_model!("rgb8", slider_rgb8);
+        if model == "hex" {
+            // edited as text, see `set_hex`
+            return Ok(Vec::new());
+        }
         impl_model!("hsv", slider_hsv);
         impl_model!("hsl", slider_hsl);
+        impl_model!("hwb", slider_hwb);
         impl_model!("lab", slider_lab);
         impl_model!("xyz", slider_xyz);
         impl_model!("lch", slider_lch);
-        None
+        impl_model!("oklab", slider_oklab);
+        impl_model!("oklch", slider_oklch);
+        impl_model!("luv", slider_luv);
+        impl_model!("lchuv", slider_lchuv);
+        impl_model!("hsluv", slider_hsluv);
+        Err(GameError::UnknownModel(model.to_string()))
     }
 
-    pub fn switch_model(&mut self, reference: &str) {
+    pub fn switch_model(&mut self, reference: &str) -> Result<(), GameError> {
         macro_rules! cross_propagate_one {
             ($from:ident, $to:ident) => {
-                chooser_convert(&self.$from.0, &self.$to.0, &self.$from.1, &mut self.$to.1);
+                chooser_convert_mapped(
+                    &self.$from.0,
+                    &self.$to.0,
+                    &self.$from.1,
+                    &mut self.$to.1,
+                    self.gamut_mapping,
+                );
             };
         }
         macro_rules! cross_propagate {
@@ -200,103 +625,218 @@ impl GameContext {
         }
 
         match reference {
+            "rgb8" | "hex" => {
+                self.propagate_rgb8();
+                cross_propagate!(slider_srgb => slider_hsv, slider_hsl, slider_hwb, slider_lab, slider_xyz, slider_lch, slider_oklab, slider_oklch, slider_luv, slider_lchuv, slider_hsluv);
+                return Ok(());
+            }
             "srgb" => {
-                cross_propagate!(slider_srgb =>  slider_hsv, slider_hsl, slider_lab, slider_xyz, slider_lch);
+                cross_propagate!(slider_srgb => slider_hsv, slider_hsl, slider_hwb, slider_lab, slider_xyz, slider_lch, slider_oklab, slider_oklch, slider_luv, slider_lchuv, slider_hsluv);
             }
             "hsv" => {
-                cross_propagate!(slider_hsv => slider_srgb, slider_hsl, slider_lab, slider_xyz, slider_lch);
+                cross_propagate!(slider_hsv => slider_srgb, slider_hsl, slider_hwb, slider_lab, slider_xyz, slider_lch, slider_oklab, slider_oklch, slider_luv, slider_lchuv, slider_hsluv);
             }
             "hsl" => {
-                cross_propagate!(slider_hsl => slider_srgb, slider_hsv, slider_lab, slider_xyz, slider_lch);
+                cross_propagate!(slider_hsl => slider_srgb, slider_hsv, slider_hwb, slider_lab, slider_xyz, slider_lch, slider_oklab, slider_oklch, slider_luv, slider_lchuv, slider_hsluv);
+            }
+            "hwb" => {
+                cross_propagate!(slider_hwb => slider_srgb, slider_hsv, slider_hsl, slider_lab, slider_xyz, slider_lch, slider_oklab, slider_oklch, slider_luv, slider_lchuv, slider_hsluv);
             }
             "lab" => {
-                cross_propagate!(slider_lab => slider_srgb, slider_hsv, slider_hsl, slider_xyz, slider_lch);
+                cross_propagate!(slider_lab => slider_srgb, slider_hsv, slider_hsl, slider_hwb, slider_xyz, slider_lch, slider_oklab, slider_oklch, slider_luv, slider_lchuv, slider_hsluv);
             }
             "xyz" => {
-                cross_propagate!(slider_xyz => slider_srgb, slider_hsv, slider_hsl, slider_lab, slider_lch);
+                cross_propagate!(slider_xyz => slider_srgb, slider_hsv, slider_hsl, slider_hwb, slider_lab, slider_lch, slider_oklab, slider_oklch, slider_luv, slider_lchuv, slider_hsluv);
             }
             "lch" => {
-                cross_propagate!(slider_lch => slider_srgb, slider_hsv, slider_hsl, slider_lab, slider_xyz);
+                cross_propagate!(slider_lch => slider_srgb, slider_hsv, slider_hsl, slider_hwb, slider_lab, slider_xyz, slider_oklab, slider_oklch, slider_luv, slider_lchuv, slider_hsluv);
+            }
+            "oklab" => {
+                cross_propagate!(slider_oklab => slider_srgb, slider_hsv, slider_hsl, slider_hwb, slider_lab, slider_xyz, slider_lch, slider_oklch, slider_luv, slider_lchuv, slider_hsluv);
+            }
+            "luv" => {
+                cross_propagate!(slider_luv => slider_srgb, slider_hsv, slider_hsl, slider_hwb, slider_lab, slider_xyz, slider_lch, slider_oklab, slider_oklch, slider_lchuv, slider_hsluv);
             }
-            _ => {}
+            "lchuv" => {
+                cross_propagate!(slider_lchuv => slider_srgb, slider_hsv, slider_hsl, slider_hwb, slider_lab, slider_xyz, slider_lch, slider_oklab, slider_oklch, slider_luv, slider_hsluv);
+            }
+            "hsluv" => {
+                cross_propagate!(slider_hsluv => slider_srgb, slider_hsv, slider_hsl, slider_hwb, slider_lab, slider_xyz, slider_lch, slider_oklab, slider_oklch, slider_luv, slider_lchuv);
+            }
+            "oklch" => {
+                cross_propagate!(slider_oklch => slider_srgb, slider_hsv, slider_hsl, slider_hwb, slider_lab, slider_xyz, slider_lch, slider_oklab, slider_luv, slider_lchuv, slider_hsluv);
+            }
+            _ => return Err(GameError::UnknownModel(reference.to_string())),
         }
+        self.slider_rgb8.1 = RGB8Chooser::from_srgb(self.current_srgb());
+        Ok(())
     }
 
-    pub fn update_slider(&mut self, model: &str, values: &[f32]) {
-        macro_rules! cross_propagate_one {
-            ($from:ident, $to:ident) => {
-                chooser_convert(&self.$from.0, &self.$to.0, &self.$from.1, &mut self.$to.1);
+    fn propagate_rgb8(&mut self) {
+        self.slider_srgb.1 = RGBChooser::from_srgb(self.slider_rgb8.0.as_srgb(&self.slider_rgb8.1));
+    }
+
+    /// Set the `"hex"` model to a `#rrggbb` or `#rgb` code and propagate it to sRGB.
+    pub fn set_hex(&mut self, hex: &str) -> Result<(), GameError> {
+        self.slider_rgb8.1 =
+            RGB8Chooser::from_hex(hex).ok_or_else(|| GameError::InvalidHex(hex.to_string()))?;
+        self.propagate_rgb8();
+        Ok(())
+    }
+
+    pub fn current_hex(&self) -> String {
+        self.slider_rgb8.0.to_hex(&self.slider_rgb8.1)
+    }
+
+    /// Set the sliders of `model` to `values` and propagate the result to sRGB.
+    ///
+    /// Nothing is changed unless `values` has one finite number per slider.
+    pub fn update_slider(&mut self, model: &str, values: &[f32]) -> Result<(), GameError> {
+        if let Some(index) = values.iter().position(|v| !v.is_finite()) {
+            return Err(GameError::NonFiniteValue {
+                what: format!("{} sliders", model),
+                index,
+            });
+        }
+
+        macro_rules! assign {
+            ($slider:ident) => {
+                assign!($slider, |v: f32| v)
             };
+            ($slider:ident, $convert:expr) => {{
+                let sliders = &mut self.$slider.1;
+                if sliders.len() != values.len() {
+                    return Err(GameError::BadSliderCount {
+                        model: model.to_string(),
+                        expected: sliders.len(),
+                        got: values.len(),
+                    });
+                }
+                sliders.iter_mut().zip(values.iter()).for_each(|(s, v)| {
+                    s.value = $convert(*v);
+                });
+            }};
+        }
+        macro_rules! assign_and_propagate {
+            ($slider:ident) => {{
+                assign!($slider);
+                chooser_convert_mapped(
+                    &self.$slider.0,
+                    &self.slider_srgb.0,
+                    &self.$slider.1,
+                    &mut self.slider_srgb.1,
+                    self.gamut_mapping,
+                );
+            }};
         }
         match model {
-            "srgb" => {
-                self.slider_srgb
-                    .1
-                    .iter_mut()
-                    .zip(values.iter())
-                    .for_each(|(s, v)| {
-                        s.value = *v;
+            "rgb8" => {
+                if let Some(index) = values.iter().position(|v| !(0.0..=255.0).contains(v)) {
+                    return Err(GameError::OutOfRange {
+                        what: format!("{} sliders", model),
+                        index,
                     });
+                }
+                assign!(slider_rgb8, |v: f32| v.round() as u8);
+                self.propagate_rgb8();
             }
-            "hsv" => {
-                self.slider_hsv
-                    .1
-                    .iter_mut()
-                    .zip(values.iter())
-                    .for_each(|(s, v)| {
-                        s.value = *v;
-                    });
-                cross_propagate_one!(slider_hsv, slider_srgb);
-            }
-            "hsl" => {
-                self.slider_hsl.1 = values
-                    .iter()
-                    .zip(self.slider_hsl.1.iter())
-                    .map(|(v, s)| {
-                        let mut s = s.clone();
-                        s.value = *v as f32;
-                        s
-                    })
-                    .collect();
-                cross_propagate_one!(slider_hsl, slider_srgb);
-            }
-            "lab" => {
-                self.slider_lab
-                    .1
-                    .iter_mut()
-                    .zip(values.iter())
-                    .for_each(|(s, v)| {
-                        s.value = *v;
-                    });
-                cross_propagate_one!(slider_lab, slider_srgb);
-            }
-            "xyz" => {
-                self.slider_xyz
-                    .1
-                    .iter_mut()
-                    .zip(values.iter())
-                    .for_each(|(s, v)| {
-                        s.value = *v;
-                    });
-                cross_propagate_one!(slider_xyz, slider_srgb);
-            }
-            "lch" => {
-                self.slider_lch
-                    .1
-                    .iter_mut()
-                    .zip(values.iter())
-                    .for_each(|(s, v)| {
-                        s.value = *v;
-                    });
-                cross_propagate_one!(slider_lch, slider_srgb);
+            "hex" => {
+                return Err(GameError::BadSliderCount {
+                    model: model.to_string(),
+                    expected: 0,
+                    got: values.len(),
+                })
             }
-            _ => {}
+            "srgb" => assign!(slider_srgb),
+            "hsv" => assign_and_propagate!(slider_hsv),
+            "hsl" => assign_and_propagate!(slider_hsl),
+            "hwb" => assign_and_propagate!(slider_hwb),
+            "lab" => assign_and_propagate!(slider_lab),
+            "xyz" => assign_and_propagate!(slider_xyz),
+            "lch" => assign_and_propagate!(slider_lch),
+            "oklab" => assign_and_propagate!(slider_oklab),
+            "oklch" => assign_and_propagate!(slider_oklch),
+            "luv" => assign_and_propagate!(slider_luv),
+            "lchuv" => assign_and_propagate!(slider_lchuv),
+            "hsluv" => assign_and_propagate!(slider_hsluv),
+            _ => return Err(GameError::UnknownModel(model.to_string())),
         }
+        Ok(())
+    }
+
+    /// The challenge and the submitted guess, available once the round is scored.
+    fn scored(&self) -> Result<(&ColorChallenge, Srgb), GameError> {
+        let challenge = self.round.challenge()?;
+        let guess = self
+            .round
+            .guess()
+            .expect("a scored round always has a guess");
+        Ok((challenge, guess))
+    }
+
+    pub fn compute_score(&self) -> Result<f32, GameError> {
+        let (challenge, guess) = self.scored()?;
+        Ok(challenge.compute_distance(guess))
+    }
+
+    /// The target written in CSS `format`, for pasting into stylesheets once scored.
+    pub fn target_css(&self, format: &str) -> Result<String, GameError> {
+        let format = css_format(format)?;
+        let (challenge, _) = self.scored()?;
+        Ok(to_css(*challenge.target_color(), format))
+    }
+
+    /// The submitted guess written in CSS `format`.
+    pub fn guess_css(&self, format: &str) -> Result<String, GameError> {
+        let format = css_format(format)?;
+        let (_, guess) = self.scored()?;
+        Ok(to_css(guess, format))
+    }
+
+    pub fn score_breakdown(&self) -> Result<JsValue, GameError> {
+        let (challenge, guess) = self.scored()?;
+        Ok(JSScoreBreakdown(challenge.compute_breakdown(guess)).into())
     }
 
-    pub fn compute_score(&self) -> f32 {
-        self.game
-            .compute_distance(self.slider_srgb.0.compute_lab(&self.slider_srgb.1))
+    /// Submit the current color as the guess, made with `model`.
+    pub fn submit(&mut self, model: &str) -> Result<RoundResult, GameError> {
+        self.model_color(model)?;
+        self.round.submit(self.current_srgb())?;
+
+        Ok(self
+            .round
+            .result(model)
+            .expect("a scored round always has a result"))
+    }
+
+    pub fn available_metrics(&self) -> Vec<String> {
+        Metric::ALL.iter().map(|m| m.key().to_string()).collect()
+    }
+
+    pub fn metric_name(&self, metric: &str) -> Result<String, GameError> {
+        Metric::from_key(metric)
+            .map(|m| m.name().to_string())
+            .ok_or_else(|| GameError::UnknownMetric(metric.to_string()))
+    }
+
+    pub fn current_metric(&self) -> String {
+        self.round.metric().key().to_string()
+    }
+
+    pub fn set_metric(&mut self, metric: &str) -> Result<(), GameError> {
+        let metric =
+            Metric::from_key(metric).ok_or_else(|| GameError::UnknownMetric(metric.to_string()))?;
+        self.round.set_metric(metric);
+        Ok(())
+    }
+
+    pub fn compute_scores(&self) -> Result<Vec<JsValue>, GameError> {
+        let (challenge, guess) = self.scored()?;
+        Ok(challenge
+            .compute_all_distances(guess)
+            .into_iter()
+            .map(|score| JSScoreInfo::from(score).into())
+            .collect())
     }
 }
 
@@ -314,127 +854,514 @@ pub fn init_panic_hook() {
     }
 }
 
-static GAME_CONTEXT: RwLock<Option<GameContext>> = RwLock::new(None);
+fn new_game_context(mut challenge: ColorChallenge, previous: &GameContext) -> GameContext {
+    challenge.set_metric(previous.round.metric());
+    challenge.set_simulation(previous.round.simulation());
 
-#[wasm_bindgen]
-pub fn init_game() {
-    let mut game = GAME_CONTEXT.write().unwrap();
-    *game = Some(GameContext::new());
+    let mut context = GameContext::with_round(GameRound::with_delay(
+        challenge,
+        JsClock,
+        previous.round.delay(),
+    ));
+    context.gamut_mapping = previous.gamut_mapping;
+    context.exclusion = previous.exclusion;
+    context.display_simulation = previous.display_simulation;
+    context
 }
 
-#[wasm_bindgen]
-pub fn darken_target_color(by: f32) -> String {
-    let mut game = GAME_CONTEXT.write().unwrap();
-    let tg = game.as_mut().unwrap().game.target_color();
-
-    let target = if by > 0.0 {
-        tg.darken(by)
-    } else {
-        tg.lighten(-by)
-    };
-
-    srgb_to_css(&target)
+fn today_utc() -> UtcDate {
+    UtcDate::from_unix_seconds((js_sys::Date::now() / 1000.0) as i64)
 }
 
 #[wasm_bindgen]
-pub fn color_acceptable() -> Option<String> {
-    let game = GAME_CONTEXT.read().unwrap();
-    let srgb = &game.as_ref().unwrap().slider_srgb;
-    match ColorChallenge::is_excluded(&srgb.0.as_srgb(&srgb.1)) {
-        None => None,
-        Some(ExcludeReason::LowSaturation) => Some("low_saturation".to_string()),
-        Some(ExcludeReason::HighSaturation) => Some("high_saturation".to_string()),
-        Some(ExcludeReason::TooBright) => Some("too_bright".to_string()),
-        Some(ExcludeReason::TooDark) => Some("too_dark".to_string()),
-    }
+pub fn daily_date() -> String {
+    today_utc().to_string()
 }
 
 #[wasm_bindgen]
-pub fn target_color_css() -> String {
-    GAME_CONTEXT
-        .read()
-        .unwrap()
-        .as_ref()
-        .unwrap()
-        .target_color_css()
+pub fn daily_rounds() -> usize {
+    DAILY_CHALLENGE_ROUNDS
 }
 
-#[wasm_bindgen]
-pub fn current_color_css(model: &str) -> String {
-    GAME_CONTEXT
-        .read()
-        .unwrap()
-        .as_ref()
-        .unwrap()
-        .current_color_css(model)
+fn css_format(format: &str) -> Result<CssFormat, GameError> {
+    CssFormat::from_key(format).ok_or_else(|| GameError::UnknownCssFormat(format.to_string()))
 }
 
 #[wasm_bindgen]
-pub fn available_models() -> Vec<String> {
-    GAME_CONTEXT
-        .read()
-        .unwrap()
-        .as_ref()
-        .unwrap()
-        .available_models()
+pub fn available_css_formats() -> Vec<String> {
+    CssFormat::ALL.iter().map(|f| f.key().to_string()).collect()
 }
 
 #[wasm_bindgen]
-pub fn model_name(model: &str) -> String {
-    GAME_CONTEXT
-        .read()
-        .unwrap()
-        .as_ref()
-        .unwrap()
-        .model_name(model)
+pub fn available_deficiencies() -> Vec<String> {
+    Deficiency::ALL
+        .iter()
+        .map(|d| d.key().to_string())
+        .collect()
 }
 
 #[wasm_bindgen]
-pub fn model_info_link(model: &str) -> String {
-    GAME_CONTEXT
-        .read()
-        .unwrap()
-        .as_ref()
-        .unwrap()
-        .model_info_link(model)
+pub fn available_exclusion_policies() -> Vec<String> {
+    ExclusionPreset::ALL
+        .iter()
+        .map(|p| p.key().to_string())
+        .collect()
 }
 
+/// One game board with its own round and session history, a page may run as many as it likes.
 #[wasm_bindgen]
-pub fn model_sliders(model: &str) -> Option<Vec<JsValue>> {
-    GAME_CONTEXT
-        .read()
-        .unwrap()
-        .as_ref()
-        .unwrap()
-        .model_sliders(model)
+pub struct GameHandle {
+    context: GameContext,
+    session: GameSession,
+    adaptive: Option<AdaptiveDelay>,
+    recognition_trials: Vec<RecognitionTrial>,
+    palette: Option<PracticePalette>,
 }
 
-#[wasm_bindgen]
-pub fn switch_model(reference: &str) {
-    GAME_CONTEXT
-        .write()
-        .unwrap()
-        .as_mut()
-        .unwrap()
-        .switch_model(reference);
+impl Default for GameHandle {
+    fn default() -> Self {
+        Self::new()
+    }
 }
 
 #[wasm_bindgen]
-pub fn update_slider(model: &str, values: &[f32]) {
-    GAME_CONTEXT
-        .write()
-        .unwrap()
-        .as_mut()
-        .unwrap()
-        .update_slider(model, values);
-}
+impl GameHandle {
+    #[wasm_bindgen(constructor)]
+    pub fn new() -> Self {
+        Self {
+            context: GameContext::new(),
+            session: GameSession::new(),
+            adaptive: None,
+            recognition_trials: Vec::new(),
+            palette: None,
+        }
+    }
 
-#[wasm_bindgen]
-pub fn compute_score() -> f32 {
-    GAME_CONTEXT
-        .read()
-        .unwrap()
-        .as_ref()
-        .unwrap()
-        .compute_score()
+    fn start_round(&mut self, seed: ChallengeSeed) {
+        let exclusion = self.context.exclusion;
+        let challenge = match &self.palette {
+            Some(palette) => palette.challenge_with(seed, exclusion),
+            None => ColorChallenge::from_seed_with(seed, TargetDistribution::default(), exclusion),
+        };
+        self.start_challenge(challenge);
+    }
+
+    fn start_challenge(&mut self, challenge: ColorChallenge) {
+        self.context = new_game_context(challenge, &self.context);
+        self.apply_adaptive_delay();
+    }
+
+    fn apply_adaptive_delay(&mut self) {
+        if let Some(adaptive) = &self.adaptive {
+            // fresh rounds are always memorizing, so this cannot fail
+            let _ = self.context.round.set_delay(adaptive.delay());
+        }
+    }
+
+    /// Start a new random round, keeping the session and settings.
+    pub fn new_round(&mut self) {
+        self.start_round(ChallengeSeed::random(&mut OsRng));
+    }
+
+    pub fn new_round_with_seed(&mut self, seed: &str) -> Result<(), JsError> {
+        self.start_round(seed.parse()?);
+        Ok(())
+    }
+
+    /// Start a round to recall a color given as any CSS color, mapped into sRGB if needed.
+    pub fn new_round_with_color(&mut self, css: &str) -> Result<(), JsError> {
+        let target = parse_css_color(css).map_err(GameError::InvalidColor)?;
+        self.start_challenge(ColorChallenge::from_color_with(
+            reduce_chroma(target),
+            ChallengeSeed::random(&mut OsRng),
+            self.context.exclusion,
+        ));
+        Ok(())
+    }
+
+    /// Draw the targets of following rounds from a palette, given as a JSON object of names to
+    /// CSS colors, a JSON array of CSS colors or a stylesheet of custom properties.
+    ///
+    /// The current round is left as is.
+    pub fn set_practice_palette(&mut self, palette: &str) -> Result<(), JsError> {
+        let parsed = if palette.trim_start().starts_with(['{', '[']) {
+            PracticePalette::from_json(palette)
+        } else {
+            PracticePalette::from_css_variables(palette)
+        };
+        self.palette = Some(parsed.map_err(GameError::InvalidPalette)?);
+        Ok(())
+    }
+
+    /// Go back to random targets from the next round on.
+    pub fn clear_practice_palette(&mut self) {
+        self.palette = None;
+    }
+
+    /// The entries of the practice palette, empty if there is none.
+    pub fn practice_palette(&self) -> Vec<JsValue> {
+        let exclusion = self.context.exclusion;
+        self.palette
+            .iter()
+            .flat_map(|palette| palette.entries())
+            .map(|entry| JSPaletteEntry::new(entry, &exclusion).into())
+            .collect()
+    }
+
+    /// Whether the target was given or drawn from a palette rather than generated from the seed.
+    pub fn is_custom_round(&self) -> bool {
+        self.context.round.is_custom()
+    }
+
+    /// Let the delay follow a staircase on the player's scores, starting over when enabled.
+    pub fn set_adaptive_delay(&mut self, enabled: bool) -> Result<(), JsError> {
+        let phase = self.context.round.phase();
+        if phase != GamePhase::Memorize {
+            return Err(GameError::from(PhaseError {
+                action: "change the adaptive delay",
+                phase,
+            })
+            .into());
+        }
+
+        self.adaptive = enabled.then(AdaptiveDelay::default);
+        self.apply_adaptive_delay();
+        Ok(())
+    }
+
+    pub fn adaptive_delay(&self) -> bool {
+        self.adaptive.is_some()
+    }
+
+    /// The delay the player can bridge most of the time, once the staircase has settled.
+    pub fn adaptive_threshold_ms(&self) -> Option<f64> {
+        self.adaptive
+            .as_ref()?
+            .threshold()
+            .map(|t| t.as_secs_f64() * 1000.0)
+    }
+
+    /// Start round `round` of today's daily challenge, counting from 0 up to [`daily_rounds`].
+    ///
+    /// Targets are always drawn with the default distribution and exclusion policy so everyone
+    /// gets the same colors, the other settings are kept as for any round.
+    pub fn new_daily_round(&mut self, round: usize) -> Result<(), JsError> {
+        if round >= DAILY_CHALLENGE_ROUNDS {
+            return Err(GameError::NoDailyRound {
+                round,
+                rounds: DAILY_CHALLENGE_ROUNDS,
+            }
+            .into());
+        }
+        let seed = daily_seed(today_utc(), round);
+        self.start_challenge(ColorChallenge::from_seed(seed));
+        Ok(())
+    }
+
+    pub fn challenge_seed(&self) -> String {
+        self.context.challenge_seed()
+    }
+
+    /// One of `"memorize"`, `"delay"`, `"recall"` or `"scored"`.
+    pub fn phase(&self) -> String {
+        self.context.round.phase().key().to_string()
+    }
+
+    pub fn delay_ms(&self) -> f64 {
+        self.context.round.delay().as_secs_f64() * 1000.0
+    }
+
+    pub fn set_delay_ms(&mut self, delay_ms: f64) -> Result<(), JsError> {
+        if !delay_ms.is_finite() {
+            return Err(GameError::NonFiniteValue {
+                what: "the delay".to_string(),
+                index: 0,
+            }
+            .into());
+        }
+        let delay = Duration::try_from_secs_f64(delay_ms.max(0.0) / 1000.0).map_err(|_| {
+            GameError::OutOfRange {
+                what: "the delay".to_string(),
+                index: 0,
+            }
+        })?;
+        self.context.round.set_delay(delay)?;
+        Ok(())
+    }
+
+    pub fn remaining_delay_ms(&self) -> f64 {
+        self.context.round.remaining_delay().as_secs_f64() * 1000.0
+    }
+
+    /// Hide the target and start the delay, returning the time spent memorizing.
+    pub fn finish_memorizing(&mut self) -> Result<f64, JsError> {
+        let memorize_time = self.context.round.finish_memorizing()?;
+        Ok(memorize_time.as_secs_f64() * 1000.0)
+    }
+
+    pub fn memorize_ms(&self) -> Option<f64> {
+        self.context
+            .round
+            .memorize_time()
+            .map(|t| t.as_secs_f64() * 1000.0)
+    }
+
+    pub fn recall_ms(&self) -> Option<f64> {
+        self.context
+            .round
+            .recall_time()
+            .map(|t| t.as_secs_f64() * 1000.0)
+    }
+
+    pub fn darken_target_color(&self, by: f32) -> Result<String, JsError> {
+        let tg = self.context.round.target()?;
+
+        let target = if by > 0.0 {
+            tg.darken(by)
+        } else {
+            tg.lighten(-by)
+        };
+
+        Ok(self.context.shown_css(target))
+    }
+
+    pub fn color_acceptable(&self) -> Option<String> {
+        self.context
+            .round
+            .check_excluded(&self.context.current_srgb())
+            .map(|reason| exclude_reason_key(reason).to_string())
+    }
+
+    pub fn target_color_css(&self) -> Result<String, JsError> {
+        Ok(self.context.target_color_css()?)
+    }
+
+    pub fn current_color_css(&self, model: &str) -> Result<String, JsError> {
+        Ok(self.context.current_color_css(model)?)
+    }
+
+    pub fn current_color_in_gamut(&self, model: &str) -> Result<bool, JsError> {
+        Ok(self.context.current_color_in_gamut(model)?)
+    }
+
+    pub fn set_gamut_mapping(&mut self, mapping: &str) -> Result<(), JsError> {
+        Ok(self.context.set_gamut_mapping(mapping)?)
+    }
+
+    /// Simulate a color vision deficiency: `"display"` shows every color as the observer would
+    /// see it, `"scoring"` scores in the simulated space. `deficiency` of `undefined` turns it off.
+    pub fn set_cvd(
+        &mut self,
+        deficiency: Option<String>,
+        severity: f32,
+        mode: &str,
+    ) -> Result<(), JsError> {
+        Ok(self
+            .context
+            .set_cvd(deficiency.as_deref(), severity, mode)?)
+    }
+
+    pub fn cvd_deficiency(&self) -> Option<String> {
+        self.context
+            .cvd()
+            .map(|(simulation, _)| simulation.deficiency.key().to_string())
+    }
+
+    pub fn cvd_severity(&self) -> f32 {
+        self.context
+            .cvd()
+            .map(|(simulation, _)| simulation.severity)
+            .unwrap_or(1.0)
+    }
+
+    pub fn cvd_mode(&self) -> String {
+        self.context
+            .cvd()
+            .map(|(_, mode)| mode)
+            .unwrap_or_default()
+            .key()
+            .to_string()
+    }
+
+    pub fn available_models(&self) -> Vec<String> {
+        self.context.available_models()
+    }
+
+    pub fn model_name(&self, model: &str) -> Result<String, JsError> {
+        Ok(self.context.model_name(model)?)
+    }
+
+    pub fn model_info_link(&self, model: &str) -> Result<String, JsError> {
+        Ok(self.context.model_info_link(model)?)
+    }
+
+    pub fn model_sliders(&self, model: &str) -> Result<Vec<JsValue>, JsError> {
+        Ok(self.context.model_sliders(model)?)
+    }
+
+    pub fn switch_model(&mut self, reference: &str) -> Result<(), JsError> {
+        Ok(self.context.switch_model(reference)?)
+    }
+
+    pub fn set_hex(&mut self, hex: &str) -> Result<(), JsError> {
+        Ok(self.context.set_hex(hex)?)
+    }
+
+    pub fn current_hex(&self) -> String {
+        self.context.current_hex()
+    }
+
+    pub fn update_slider(&mut self, model: &str, values: &[f32]) -> Result<(), JsError> {
+        Ok(self.context.update_slider(model, values)?)
+    }
+
+    pub fn compute_score(&self) -> Result<f32, JsError> {
+        Ok(self.context.compute_score()?)
+    }
+
+    pub fn target_css(&self, format: &str) -> Result<String, JsError> {
+        Ok(self.context.target_css(format)?)
+    }
+
+    pub fn guess_css(&self, format: &str) -> Result<String, JsError> {
+        Ok(self.context.guess_css(format)?)
+    }
+
+    pub fn available_metrics(&self) -> Vec<String> {
+        self.context.available_metrics()
+    }
+
+    pub fn metric_name(&self, metric: &str) -> Result<String, JsError> {
+        Ok(self.context.metric_name(metric)?)
+    }
+
+    pub fn current_metric(&self) -> String {
+        self.context.current_metric()
+    }
+
+    pub fn set_metric(&mut self, metric: &str) -> Result<(), JsError> {
+        Ok(self.context.set_metric(metric)?)
+    }
+
+    pub fn compute_scores(&self) -> Result<Vec<JsValue>, JsError> {
+        Ok(self.context.compute_scores()?)
+    }
+
+    pub fn score_breakdown(&self) -> Result<JsValue, JsError> {
+        Ok(self.context.score_breakdown()?)
+    }
+
+    pub fn exclusion_policy(&self) -> String {
+        self.context.exclusion_policy()
+    }
+
+    pub fn set_exclusion_policy(&mut self, policy: &str) -> Result<(), JsError> {
+        Ok(self.context.set_exclusion_policy(policy)?)
+    }
+
+    /// Submit the current color as the guess made with `model`, recording the round in the
+    /// session and returning its score.
+    pub fn submit(&mut self, model: &str) -> Result<f32, JsError> {
+        let round = self.context.submit(model)?;
+        let score = round.score;
+        if let Some(adaptive) = &mut self.adaptive {
+            adaptive.record(&round);
+        }
+        self.session.push(round);
+        Ok(score)
+    }
+
+    /// Draw `alternatives` swatches for a forced choice, the target and distractors at CIEDE2000
+    /// `delta_e` from it, in random order.
+    ///
+    /// The options are drawn once per round, later calls with the same arguments return the same
+    /// ones.
+    pub fn recognition_options(
+        &mut self,
+        alternatives: usize,
+        delta_e: f32,
+    ) -> Result<Vec<String>, JsError> {
+        if !delta_e.is_finite() {
+            return Err(GameError::NonFiniteValue {
+                what: "the distractor distance".to_string(),
+                index: 0,
+            }
+            .into());
+        }
+
+        let options = self
+            .context
+            .round
+            .recognition_round(&mut OsRng, alternatives, delta_e)
+            .map_err(GameError::from)?
+            .options()
+            .to_vec();
+        Ok(options
+            .into_iter()
+            .map(|color| self.context.shown_css(color))
+            .collect())
+    }
+
+    /// Pick option `choice` of the drawn recognition options, returning whether it was the target.
+    pub fn submit_recognition(&mut self, choice: usize) -> Result<bool, JsError> {
+        let recognition = self
+            .context
+            .round
+            .recognition()
+            .cloned()
+            .ok_or(GameError::NoRecognitionRound)?;
+        let color = *recognition
+            .options()
+            .get(choice)
+            .ok_or(GameError::InvalidChoice {
+                choice,
+                options: recognition.options().len(),
+            })?;
+
+        self.context.round.submit(color).map_err(GameError::from)?;
+        let trial = recognition.check(choice);
+        self.recognition_trials.push(trial);
+        Ok(trial.correct)
+    }
+
+    /// Index of the target among the recognition options, once scored.
+    pub fn recognition_answer(&self) -> Result<usize, JsError> {
+        self.context.round.challenge().map_err(GameError::from)?;
+        let recognition = self
+            .context
+            .round
+            .recognition()
+            .ok_or(GameError::NoRecognitionRound)?;
+        Ok(recognition.answer())
+    }
+
+    pub fn recognition_stats(&self) -> JsValue {
+        RecognitionStats::from_trials(&self.recognition_trials)
+            .map(|stats| JSRecognitionStats(stats).into())
+            .unwrap_or(JsValue::NULL)
+    }
+
+    pub fn session_stats(&self) -> JsValue {
+        self.session
+            .stats()
+            .map(|stats| JSSessionStats(stats).into())
+            .unwrap_or(JsValue::NULL)
+    }
+
+    pub fn reset_session(&mut self) {
+        self.session.clear();
+    }
+
+    pub fn export_session_json(&self) -> Result<String, JsError> {
+        Ok(self.session.to_json()?)
+    }
+
+    pub fn export_session_csv(&self) -> String {
+        self.session.to_csv()
+    }
+
+    pub fn import_session_json(&mut self, json: &str) -> Result<(), JsError> {
+        self.session = GameSession::from_json(json)?;
+        Ok(())
+    }
 }
@@ -1,15 +1,16 @@
 import { useEffect, useState } from 'react'
 import './App.css'
 import game_wasm, {
-  init_game, init_panic_hook, target_color_css
-  , current_color_css, available_models, model_name, model_sliders, switch_model, update_slider,
-  model_info_link,
-  compute_score,
-  color_acceptable,
-  darken_target_color
+  init_panic_hook,
+  available_exclusion_policies,
+  available_deficiencies,
+  available_css_formats,
+  daily_date,
+  daily_rounds,
+  GameHandle
 } from '../../pkg'
 import './i18n'
-import { Alert, Box, Button, Divider, FormControl, FormGroup, FormLabel, Link, MenuItem, Paper, Radio, RadioGroup, Select, Slider, Stack, Typography } from '@mui/material'
+import { Alert, Box, Button, Checkbox, Divider, FormControl, FormControlLabel, FormGroup, FormLabel, Link, MenuItem, Paper, Radio, RadioGroup, Select, Slider, Stack, TextField, Typography } from '@mui/material'
 import { useTranslation } from 'react-i18next'
 
 function ColorSampleBlock(props: { color: string, size: number, showText?: boolean }) {
@@ -72,10 +73,184 @@ interface SliderInfo {
   value: number
   min: number
   max: number
+  integer: boolean
 }
 
-function Step4(props: { reset: () => void, lastModel: string, recall_ms: number }) {
+interface PaletteEntry {
+  name: string
+  css: string
+  excluded?: string
+}
+
+function HexInput(props: { game: GameHandle, onChange: () => void }) {
+  const { t } = useTranslation();
+  const [text, setText] = useState(props.game.current_hex())
+  const [valid, setValid] = useState(true)
+  return (
+    <Box sx={{ margin: 1 }}>
+      <TextField label={props.game.model_name('hex')} value={text} error={!valid}
+        helperText={valid ? null : t('invalid_hex')}
+        onChange={(e) => {
+          setText(e.target.value)
+          try {
+            props.game.set_hex(e.target.value)
+            setValid(true)
+            props.onChange()
+          } catch {
+            setValid(false)
+          }
+        }} />
+    </Box>
+  )
+}
+
+function ScoreFeedback(props: { game: GameHandle }) {
+  const { t } = useTranslation();
+  // components are weighted so that 1.0 is about one just noticeable difference
+  const breakdown = props.game.score_breakdown()
+  const judge = (value: number, below: string, above: string, ok: string) =>
+    value < -1 ? t(below) : value > 1 ? t(above) : t(ok)
+
+  return (
+    <Box>
+      <Typography variant="body1">{judge(breakdown.weighted_l, 'feedback_too_dark', 'feedback_too_light', 'feedback_lightness_ok')}</Typography>
+      <Typography variant="body1">{judge(breakdown.weighted_c, 'feedback_too_dull', 'feedback_too_vivid', 'feedback_chroma_ok')}</Typography>
+      <Typography variant="body1">{judge(breakdown.weighted_h, 'feedback_hue_off', 'feedback_hue_off', 'feedback_hue_ok')}</Typography>
+      <Typography variant="body2">
+        {`${t('feedback_breakdown')}: ΔE00 ${breakdown.delta_e.toFixed(2)}, ` +
+          `ΔL' ${breakdown.delta_l.toFixed(2)}, ΔC' ${breakdown.delta_c.toFixed(2)}, ΔH' ${breakdown.delta_h.toFixed(2)}`}
+      </Typography>
+    </Box>
+  )
+}
+
+function download(filename: string, mime: string, content: string) {
+  const url = URL.createObjectURL(new Blob([content], { type: mime }))
+  const a = document.createElement('a')
+  a.href = url
+  a.download = filename
+  a.click()
+  URL.revokeObjectURL(url)
+}
+
+function SessionSummary(props: { game: GameHandle }) {
+  const { t } = useTranslation();
+  const stats = props.game.session_stats()
+  if (!stats) {
+    return null
+  }
+
+  return (
+    <Box>
+      <Typography variant="h6">{t('session_stats') + ` (${stats.count})`}</Typography>
+      <Typography variant="body2">
+        {`${t('session_mean')}: ${stats.mean.toFixed(2)}, ${t('session_median')}: ${stats.median.toFixed(2)}, ` +
+          `${t('session_best')}: ${stats.best.toFixed(2)}, ${t('session_worst')}: ${stats.worst.toFixed(2)}, σ: ${stats.std_dev.toFixed(2)}`}
+      </Typography>
+      <Button variant="outlined" onClick={() => download('color-recall.json', 'application/json', props.game.export_session_json())} sx={{ margin: 1 }}>
+        {t('btn_export_json')}
+      </Button>
+      <Button variant="outlined" onClick={() => download('color-recall.csv', 'text/csv', props.game.export_session_csv())} sx={{ margin: 1 }}>
+        {t('btn_export_csv')}
+      </Button>
+    </Box>
+  )
+}
+
+const RECOGNITION_ALTERNATIVES = 4
+const RECOGNITION_DELTA_ES = [1, 2, 3, 5, 8]
+
+function RecognitionSummary(props: { game: GameHandle }) {
+  const { t } = useTranslation();
+  const stats = props.game.recognition_stats()
+  if (!stats) {
+    return null
+  }
+  return (
+    <Box>
+      <Typography variant="h6">{t('recognition_accuracy')}</Typography>
+      {stats.levels.map((level: any) => (
+        <Typography variant="body2" key={level.delta_e}>
+          {`ΔE ${level.delta_e}: ${level.correct}/${level.trials} (${(level.accuracy * 100).toFixed(0)}%)`}
+        </Typography>
+      ))}
+      {
+        stats.threshold !== null ?
+          <Typography variant="body1">{t('recognition_threshold') + stats.threshold.toFixed(1)}</Typography>
+          : null
+      }
+    </Box>
+  )
+}
+
+function CssExport(props: { game: GameHandle }) {
+  const { t } = useTranslation();
+  const [format, setFormat] = useState('hex')
+
+  return (
+    <Box>
+      <Typography variant="h6">{t('css_export')}</Typography>
+      <Select value={format} onChange={(e) => setFormat(e.target.value as string)}>
+        {available_css_formats().map((f) => (
+          <MenuItem key={f} value={f}>{t(`css_${f}`)}</MenuItem>
+        ))}
+      </Select>
+      <Typography variant="body2" sx={{ fontFamily: 'monospace' }}>{t('target_color') + ': ' + props.game.target_css(format)}</Typography>
+      <Typography variant="body2" sx={{ fontFamily: 'monospace' }}>{t('your_color') + ': ' + props.game.guess_css(format)}</Typography>
+    </Box>
+  )
+}
+
+function ChallengeInfo(props: { game: GameHandle, daily: number | null }) {
+  const { t } = useTranslation();
+  return (
+    <>
+      {
+        props.daily !== null ?
+          <Typography variant="body1">{`${t('daily_challenge')} ${daily_date()}: ${props.daily + 1}/${daily_rounds()}`}</Typography>
+          : null
+      }
+      {
+        props.game.is_custom_round() ?
+          null
+          : <Typography variant="body2">{t('challenge_seed') + props.game.challenge_seed()}</Typography>
+      }
+    </>
+  )
+}
+
+function Step4(props: { game: GameHandle, reset: () => void, lastModel: string, recall_ms: number, recognition?: boolean, correct?: boolean, chosen?: string, daily: number | null }) {
   const { t } = useTranslation();
+  const [updateCount, setUpdateCount] = useState(0)
+  if (props.recognition) {
+    return (
+      <Box>
+        <Typography variant="h4">{t('step_4_score')}</Typography>
+        <Typography variant="h6">
+          {props.correct ? t('recognition_correct') : t('recognition_wrong') + (props.game.recognition_answer() + 1)}
+        </Typography>
+        <Divider sx={{ margin: 2 }} />
+
+        <Typography variant="h6">{t('target_color')}</Typography>
+        <ColorSampleBlock showText color={props.game.target_color_css()} size={100} />
+
+        <Typography variant="h6">{t('your_color')}</Typography>
+        <ColorSampleBlock showText color={props.chosen ?? ''} size={100} />
+
+        <Typography variant="body1">{t('time_taken_recall') + (props.recall_ms / 1000).toFixed(1)}s</Typography>
+        <ChallengeInfo game={props.game} daily={props.daily} />
+
+        <Divider sx={{ margin: 2 }} />
+
+        <RecognitionSummary game={props.game} />
+
+        <Divider sx={{ margin: 2 }} />
+
+        <ExclusionPicker game={props.game} onChange={() => setUpdateCount(updateCount + 1)} />
+        <NextBtn reset={props.reset} />
+      </Box>
+    )
+  }
   return (
     <Box>
       <Typography variant="h4">{t('step_4_score')}</Typography>
@@ -83,24 +258,44 @@ function Step4(props: { reset: () => void, lastModel: string, recall_ms: number
       <Divider sx={{ margin: 2 }} />
 
       <Typography variant="h6">{t('target_color')}</Typography>
-      <ColorSampleBlock showText color={target_color_css()} size={100} />
+      <ColorSampleBlock showText color={props.game.target_color_css()} size={100} />
 
       <Typography variant="h6">{t('your_color')}</Typography>
-      <ColorSampleBlock showText color={current_color_css('srgb')} size={100} />
+      <ColorSampleBlock showText color={props.game.current_color_css('srgb')} size={100} />
+
+      <CssExport game={props.game} />
 
       <Divider sx={{ margin: 2 }} />
 
-      <Typography variant="h6">{`CIEDE2000 Delta E, ${t('lower_is_better')}`}</Typography>
-      <Typography variant="body1">{compute_score()}</Typography>
+      <Typography variant="h6">{`${props.game.metric_name(props.game.current_metric())}, ${t('lower_is_better')}`}</Typography>
+      <Typography variant="body1">{props.game.compute_score()}</Typography>
+      <ScoreFeedback game={props.game} />
+
+      <Typography variant="h6">{t('other_metrics')}</Typography>
+      {props.game.compute_scores().map((score) => (
+        <Typography variant="body2" key={score.metric}>{`${score.name}: ${score.value.toFixed(2)}`}</Typography>
+      ))}
 
       <Divider sx={{ margin: 2 }} />
 
       <Typography variant="body1">{t('last_model') + t(props.lastModel)}</Typography>
 
       <Typography variant="body1">{t('time_taken_recall') + (props.recall_ms / 1000).toFixed(1)}s</Typography>
+      <ChallengeInfo game={props.game} daily={props.daily} />
+
+      {
+        props.game.adaptive_threshold_ms() !== undefined ?
+          <Typography variant="body1">{t('adaptive_threshold') + (props.game.adaptive_threshold_ms()! / 1000).toFixed(1)}s</Typography>
+          : null
+      }
+
+      <Divider sx={{ margin: 2 }} />
+
+      <SessionSummary game={props.game} />
 
       <Divider sx={{ margin: 2 }} />
 
+      <ExclusionPicker game={props.game} onChange={() => setUpdateCount(updateCount + 1)} />
       <NextBtn reset={props.reset} />
 
     </Box>
@@ -111,50 +306,60 @@ function Step3(props: {
   next: (
     lastModel: string,
     recall_ms: number
-  ) => void, reset: () => void
+  ) => void, reset: () => void, game: GameHandle
 }) {
   const { t } = useTranslation();
 
-  const [beginRecall, setBeginRecall] = useState(0)
   const [availableModels, setAvailableModels] = useState<string[]>([])
   const [sliderMap, setSliderMap] = useState(new Map<string, SliderInfo[]>())
   const [updateCount, setUpdateCount] = useState(0)
   const [currentModel, setCurrentModel] = useState("")
-  const [transferArray, _] = useState(new Float32Array([0.0, 0.0, 0.0, 0.0]))
+  const [errorMessage, setErrorMessage] = useState("")
+  // the score is always read from the sRGB sliders, which is also what is shown before a model is picked
+  const activeModel = currentModel || 'srgb'
+
+  const tryGame = (f: () => void) => {
+    try {
+      f()
+      setErrorMessage("")
+    } catch (e) {
+      setErrorMessage((e as Error).message)
+    }
+  }
 
   if (availableModels.length === 0) {
-    setBeginRecall(+new Date())
-    const ms = available_models()
+    const ms = props.game.available_models()
     setAvailableModels(ms)
-    switch_model('srgb')
+    props.game.switch_model('srgb')
     setUpdateCount(updateCount + 1)
     ms.forEach((model) => {
-      const sliders = model_sliders(model)
-      if (sliders) {
+      try {
+        const sliders = props.game.model_sliders(model)
         setSliderMap((prev) => {
           prev.set(model, sliders.map((s) => {
             return {
               name: s.name,
               value: s.value,
               min: s.min,
-              max: s.max
+              max: s.max,
+              integer: s.integer
             }
           }))
           return new Map(prev)
         })
-      } else {
-        console.error(`No sliders for model ${model}`)
+      } catch (e) {
+        console.error(`No sliders for model ${model}: ${(e as Error).message}`)
       }
     })
   }
 
-  const unacceptable_reason = color_acceptable()
+  const unacceptable_reason = props.game.color_acceptable()
 
   return (
     <Box>
       <Typography variant="h4">{t('step_3_recall')}</Typography>
       <Typography variant="h6">{t('step_3_instructions')}</Typography>
-      <ColorSampleBlock color={current_color_css(currentModel)} size={100} />
+      <ColorSampleBlock color={props.game.current_color_css(activeModel)} size={100} />
       <Divider sx={{ margin: 2 }} />
       <FormControl>
         <FormLabel>{t('colorspace')}</FormLabel>
@@ -165,29 +370,25 @@ function Step3(props: {
               <FormGroup key={model}>
                 <Radio
                   value={model}
-                  onChange={() => {
-                    switch_model(currentModel)
+                  onChange={() => tryGame(() => {
+                    props.game.switch_model(activeModel)
                     setCurrentModel(model)
                     availableModels.forEach((m) => {
-                      const newSliders = model_sliders(m)
-                      if (newSliders) {
-                        newSliders.forEach((s, i) => {
-                          transferArray[i] = s.value
-                        })
-                        sliderMap.set(m, newSliders.map((s) => {
-                          return {
-                            name: s.name,
-                            value: s.value,
-                            min: s.min,
-                            max: s.max
-                          }
-                        }))
-                      }
+                      const newSliders = props.game.model_sliders(m)
+                      sliderMap.set(m, newSliders.map((s) => {
+                        return {
+                          name: s.name,
+                          value: s.value,
+                          min: s.min,
+                          max: s.max,
+                          integer: s.integer
+                        }
+                      }))
                     })
-                  }}
+                  })}
                 />
-                <Typography variant="body1">{model_name(model)}</Typography>
-                <Link href={model_info_link(model)} target="_blank" rel="noreferrer"> Info </Link>
+                <Typography variant="body1">{props.game.model_name(model)}</Typography>
+                <Link href={props.game.model_info_link(model)} target="_blank" rel="noreferrer"> Info </Link>
               </FormGroup>
             )
           })}
@@ -198,10 +399,13 @@ function Step3(props: {
           if (model !== currentModel) {
             return null
           }
+          if (model === 'hex') {
+            return <HexInput key={model} game={props.game} onChange={() => setUpdateCount(updateCount + 1)} />
+          }
           const sliders = sliderMap.get(model)
           return sliders ? (
             <Box key={model} sx={{ display: 'inline-block', margin: 1 }}>
-              <Typography variant="h6">{model_name(model)}</Typography>
+              <Typography variant="h6">{props.game.model_name(model)}</Typography>
               <Stack spacing={2}>
                 {sliders.map((slider, j) => {
                   return (
@@ -215,15 +419,12 @@ function Step3(props: {
                             const new_value = v as number;
                             sliders.find(s => s.name === slider.name)!.value = new_value;
                             sliderMap.set(model, sliders)
-                            sliders.forEach((s, i) => {
-                              transferArray[i] = s.value
-                            });
-                            update_slider(model, transferArray)
+                            tryGame(() => props.game.update_slider(model, new Float32Array(sliders.map(s => s.value))))
                             setUpdateCount(updateCount + 1)
                           }}
                           min={slider.min}
                           max={slider.max}
-                          step={(slider.max - slider.min) / 128}
+                          step={slider.integer ? 1 : (slider.max - slider.min) / 128}
                         />
                       </Stack>
                     </Box>
@@ -234,6 +435,20 @@ function Step3(props: {
             </Box>
           ) : null
         })}
+        {
+          currentModel && !props.game.current_color_in_gamut(currentModel) ? (
+            <Box>
+              <Alert severity='info'>{t('out_of_gamut')}</Alert>
+            </Box>
+          ) : null
+        }
+        {
+          errorMessage ? (
+            <Box>
+              <Alert severity='error'>{errorMessage}</Alert>
+            </Box>
+          ) : null
+        }
         {
           unacceptable_reason ? (
             <Box>
@@ -246,30 +461,72 @@ function Step3(props: {
       </Box>
 
       <Divider sx={{ margin: 1 }} />
-      <NextBtn next={() => props.next(model_name(currentModel), +new Date() - beginRecall)} reset={props.reset} />
+      <NextBtn next={() => {
+        tryGame(() => {
+          props.game.submit(activeModel)
+          props.next(props.game.model_name(activeModel), props.game.recall_ms() ?? 0)
+        })
+      }} reset={props.reset} />
     </Box>
   )
 
 }
 
-function Step2(props: { next: () => void, reset: () => void }) {
+function Step3Recognition(props: {
+  next: (correct: boolean, recall_ms: number, chosen: string) => void, reset: () => void, game: GameHandle, delta_e: number
+}) {
+  const { t } = useTranslation();
+  const [error, setError] = useState<string | null>(null)
+  const [options, setOptions] = useState<string[]>([])
+
+  useEffect(() => {
+    try {
+      setOptions(props.game.recognition_options(RECOGNITION_ALTERNATIVES, props.delta_e))
+    } catch (e) {
+      setError(String(e))
+    }
+  }, [props.game, props.delta_e])
+
+  return (
+    <Box>
+      <Typography variant="h4">{t('step_3_recall')}</Typography>
+      <Typography variant="h6">{t('step_3_recognition_instructions')}</Typography>
+      {error ? <Alert severity='error'>{error}</Alert> : null}
+      <Stack direction="row" spacing={2} sx={{ margin: 2 }}>
+        {options.map((color, i) => (
+          <Button key={i} onClick={() => {
+            try {
+              const correct = props.game.submit_recognition(i)
+              props.next(correct, props.game.recall_ms() ?? 0, color)
+            } catch (e) {
+              setError(String(e))
+            }
+          }}>
+            <ColorSampleBlock color={color} size={100} />
+          </Button>
+        ))}
+      </Stack>
+      <Divider sx={{ margin: 1 }} />
+      <NextBtn reset={props.reset} />
+    </Box>
+  )
+}
+
+function Step2(props: { game: GameHandle, next: () => void, reset: () => void }) {
   const { t } = useTranslation();
-  const delay = 8000
-  const [remaining, setRemaining] = useState(delay)
-  const [begin, _] = useState(+new Date())
+  const [remaining, setRemaining] = useState(props.game.remaining_delay_ms())
 
   useEffect(() => {
     const interval = setInterval(() => {
-      const elapsed = +new Date() - begin;
-      setRemaining(delay - elapsed);
-      if (elapsed >= delay) {
+      setRemaining(props.game.remaining_delay_ms());
+      if (props.game.phase() === 'recall') {
         props.next();
       }
     }, 100)
 
 
     return () => clearInterval(interval)
-  }, [begin])
+  }, [props.game])
 
   return (
     <Box>
@@ -279,14 +536,182 @@ function Step2(props: { next: () => void, reset: () => void }) {
   )
 }
 
-function Step1(props: { next: () => void, reset: () => void }) {
+function ExclusionPicker(props: { game: GameHandle, onChange: () => void }) {
+  const { t } = useTranslation();
+  return (
+    <FormControl sx={{ margin: 1 }}>
+      <FormLabel>{t('exclusion_policy')}</FormLabel>
+      <Select value={props.game.exclusion_policy()} onChange={(e) => {
+        props.game.set_exclusion_policy(e.target.value as string)
+        props.onChange()
+      }}>
+        {available_exclusion_policies().map((policy) => (
+          <MenuItem key={policy} value={policy}>{t(`exclusion_${policy}`)}</MenuItem>
+        ))}
+      </Select>
+    </FormControl>
+  )
+}
+
+function MetricPicker(props: { game: GameHandle, onChange: () => void }) {
+  const { t } = useTranslation();
+  return (
+    <FormControl sx={{ margin: 1 }}>
+      <FormLabel>{t('metric')}</FormLabel>
+      <Select value={props.game.current_metric()} onChange={(e) => {
+        props.game.set_metric(e.target.value as string)
+        props.onChange()
+      }}>
+        {props.game.available_metrics().map((metric) => (
+          <MenuItem key={metric} value={metric}>{props.game.metric_name(metric)}</MenuItem>
+        ))}
+      </Select>
+    </FormControl>
+  )
+}
+
+function CvdPicker(props: { game: GameHandle, onChange: () => void }) {
+  const { t } = useTranslation();
+  const deficiency = props.game.cvd_deficiency() ?? 'none'
+  const update = (deficiency: string, severity: number, mode: string) => {
+    props.game.set_cvd(deficiency === 'none' ? undefined : deficiency, severity, mode)
+    props.onChange()
+  }
+  return (
+    <FormControl sx={{ margin: 1, minWidth: 200 }}>
+      <FormLabel>{t('cvd')}</FormLabel>
+      <Select value={deficiency} onChange={(e) =>
+        update(e.target.value as string, props.game.cvd_severity(), props.game.cvd_mode())
+      }>
+        <MenuItem value='none'>{t('cvd_none')}</MenuItem>
+        {available_deficiencies().map((d) => (
+          <MenuItem key={d} value={d}>{t(`cvd_${d}`)}</MenuItem>
+        ))}
+      </Select>
+      {
+        deficiency !== 'none' ?
+          <>
+            <Typography variant="body2">{t('cvd_severity') + props.game.cvd_severity().toFixed(1)}</Typography>
+            <Slider min={0} max={1} step={0.1} value={props.game.cvd_severity()} onChange={(_, value) =>
+              update(deficiency, value as number, props.game.cvd_mode())
+            } />
+            <RadioGroup row value={props.game.cvd_mode()} onChange={(e) =>
+              update(deficiency, props.game.cvd_severity(), e.target.value)
+            }>
+              <FormControlLabel value='display' control={<Radio />} label={t('cvd_mode_display')} />
+              <FormControlLabel value='scoring' control={<Radio />} label={t('cvd_mode_scoring')} />
+            </RadioGroup>
+          </>
+          : null
+      }
+    </FormControl>
+  )
+}
+
+function PracticePicker(props: { game: GameHandle, onChange: () => void }) {
+  const { t } = useTranslation();
+  const [color, setColor] = useState('')
+  const [palette, setPalette] = useState('')
+  const [error, setError] = useState<string | null>(null)
+  const entries: PaletteEntry[] = props.game.practice_palette()
+
+  const attempt = (action: () => void) => {
+    try {
+      action()
+      setError(null)
+      props.onChange()
+    } catch (e) {
+      setError(String(e))
+    }
+  }
+
+  return (
+    <FormControl sx={{ margin: 1, minWidth: 300 }}>
+      <FormLabel>{t('practice')}</FormLabel>
+      <Stack direction="row" spacing={1} sx={{ marginTop: 1 }}>
+        <TextField size="small" label={t('practice_color')} value={color} onChange={(e) => setColor(e.target.value)} />
+        <Button variant="outlined" onClick={() => attempt(() => props.game.new_round_with_color(color))}>
+          {t('practice_color_start')}
+        </Button>
+      </Stack>
+      <TextField multiline minRows={3} sx={{ marginTop: 1 }} label={t('practice_palette')}
+        placeholder={'--brand-red: #c8102e;\n--brand-blue: oklch(0.45 0.15 260);'}
+        value={palette} onChange={(e) => setPalette(e.target.value)} />
+      <Stack direction="row" spacing={1} sx={{ marginTop: 1 }}>
+        <Button variant="outlined" onClick={() => attempt(() => {
+          props.game.set_practice_palette(palette)
+          props.game.new_round()
+        })}>
+          {t('practice_palette_use')}
+        </Button>
+        <Button variant="outlined" disabled={entries.length === 0} onClick={() => attempt(() => {
+          props.game.clear_practice_palette()
+          props.game.new_round()
+        })}>
+          {t('practice_palette_clear')}
+        </Button>
+      </Stack>
+      {error ? <Alert severity='error'>{error}</Alert> : null}
+      {entries.map((entry) => (
+        <Stack direction="row" spacing={1} alignItems="center" key={entry.name}>
+          <ColorSampleBlock color={entry.css} size={16} />
+          <Typography variant="body2">{entry.name}</Typography>
+          {
+            entry.excluded ?
+              <Typography variant="body2" color="warning.main">
+                {t('practice_excluded') + (t('unacceptable') as any)(entry.excluded)}
+              </Typography>
+              : null
+          }
+        </Stack>
+      ))}
+    </FormControl>
+  )
+}
+
+function ModePicker(props: { deltaE: number, onChange: (deltaE: number) => void }) {
+  const { t } = useTranslation();
+  return (
+    <FormControl sx={{ margin: 1 }}>
+      <FormLabel>{t('mode')}</FormLabel>
+      <Select value={props.deltaE} onChange={(e) => props.onChange(Number(e.target.value))}>
+        <MenuItem value={0}>{t('mode_recall')}</MenuItem>
+        {RECOGNITION_DELTA_ES.map((deltaE) => (
+          <MenuItem key={deltaE} value={deltaE}>{t('mode_recognition') + deltaE}</MenuItem>
+        ))}
+      </Select>
+    </FormControl>
+  )
+}
+
+function Step1(props: { game: GameHandle, next: () => void, reset: () => void, recognitionDeltaE: number, setRecognitionDeltaE: (deltaE: number) => void, daily: number | null, startDaily: () => void }) {
   const { t } = useTranslation();
   const [showCalibration, setShowCalibration] = useState(true)
+  const [updateCount, setUpdateCount] = useState(0)
   return (
     <Box>
       <Typography variant="h4">{t('step_1_memorization')}</Typography>
       <Typography variant="h6">{t('step_1_instructions')}</Typography>
-      <ColorSampleBlock color={target_color_css()} size={100} />
+      {
+        props.daily !== null ?
+          <Typography variant="body1">{`${t('daily_challenge')} ${daily_date()}: ${props.daily + 1}/${daily_rounds()}`}</Typography>
+          : <Button variant="outlined" onClick={props.startDaily} sx={{ margin: 1 }}>
+            {`${t('daily_start')} (${daily_date()})`}
+          </Button>
+      }
+      <ModePicker deltaE={props.recognitionDeltaE} onChange={props.setRecognitionDeltaE} />
+      <MetricPicker game={props.game} onChange={() => setUpdateCount(updateCount + 1)} />
+      <CvdPicker game={props.game} onChange={() => setUpdateCount(updateCount + 1)} />
+      <PracticePicker game={props.game} onChange={() => setUpdateCount(updateCount + 1)} />
+      <FormControlLabel sx={{ margin: 1 }} label={t('adaptive_delay')} control={
+        <Checkbox checked={props.game.adaptive_delay()} onChange={(e) => {
+          props.game.set_adaptive_delay(e.target.checked)
+          setUpdateCount(updateCount + 1)
+        }} />
+      } />
+      <Box>
+        <ColorSampleBlock color={props.game.target_color_css()} size={100} />
+      </Box>
       <Divider sx={{ margin: 2 }} />
       <Typography variant="h6">{t('calibration')}</Typography>
       <Typography variant="body1">
@@ -302,7 +727,7 @@ function Step1(props: { next: () => void, reset: () => void }) {
               <Divider sx={{ margin: 2 }} />
               <RainbowScale size={150} />
               <Divider sx={{ margin: 2 }} />
-              <GradientScale size={150} from={darken_target_color(-0.25)} to={darken_target_color(0.25)} />
+              <GradientScale size={150} from={props.game.darken_target_color(-0.25)} to={props.game.darken_target_color(0.25)} />
             </>
           ) : null
       }
@@ -326,15 +751,35 @@ function LanguagePicker() {
   )
 }
 
-function Game() {
+function Game(props: { game: GameHandle }) {
   const { t } = useTranslation();
   const [step, setStep] = useState(1)
   const [updateCount, setUpdateCount] = useState(0)
   const [recallTime, setRecallTime] = useState(0)
   const [lastModel, setLastModel] = useState("")
+  // 0 plays the usual recall with sliders, otherwise the distractor ΔE of a forced choice
+  const [recognitionDeltaE, setRecognitionDeltaE] = useState(0)
+  const [recognitionCorrect, setRecognitionCorrect] = useState(false)
+  // the option picked in a forced choice, the sliders are not used then
+  const [recognitionChoice, setRecognitionChoice] = useState("")
+  // index of the daily challenge round being played, null for random rounds
+  const [daily, setDaily] = useState<number | null>(null)
 
   const doReset = () => {
-    init_game()
+    if (daily !== null && daily + 1 < daily_rounds()) {
+      props.game.new_daily_round(daily + 1)
+      setDaily(daily + 1)
+    } else {
+      props.game.new_round()
+      setDaily(null)
+    }
+    setStep(1)
+    setUpdateCount(updateCount + 1)
+  }
+
+  const startDaily = () => {
+    props.game.new_daily_round(0)
+    setDaily(0)
     setStep(1)
     setUpdateCount(updateCount + 1)
   }
@@ -347,17 +792,31 @@ function Game() {
       <Divider sx={{ margin: 2 }} />
       {
         (step === 1) ?
-          (<Step1 next={() => setStep(2)} reset={doReset} />)
+          (<Step1 game={props.game} next={() => {
+            props.game.finish_memorizing()
+            setStep(2)
+          }} reset={doReset} recognitionDeltaE={recognitionDeltaE} setRecognitionDeltaE={setRecognitionDeltaE}
+            daily={daily} startDaily={startDaily} />)
           : null
       }
       {
         (step === 2) ?
-          (<Step2 next={() => setStep(3)} reset={doReset} />)
+          (<Step2 game={props.game} next={() => setStep(3)} reset={doReset} />)
+          : null
+      }
+      {
+        (step === 3 && recognitionDeltaE > 0) ?
+          (<Step3Recognition game={props.game} delta_e={recognitionDeltaE} next={(correct, recall_ms, chosen) => {
+            setRecognitionCorrect(correct)
+            setRecognitionChoice(chosen)
+            setRecallTime(recall_ms)
+            setStep(4)
+          }} reset={doReset} />)
           : null
       }
       {
-        (step === 3) ?
-          (<Step3 next={(model, recall_ms) => {
+        (step === 3 && recognitionDeltaE === 0) ?
+          (<Step3 game={props.game} next={(model, recall_ms) => {
             setLastModel(model)
             setRecallTime(recall_ms)
             setStep(4)
@@ -366,7 +825,8 @@ function Game() {
       }
       {
         (step === 4) ?
-          (<Step4 reset={doReset} lastModel={lastModel} recall_ms={recallTime} />)
+          (<Step4 game={props.game} reset={doReset} lastModel={lastModel} recall_ms={recallTime}
+            recognition={recognitionDeltaE > 0} correct={recognitionCorrect} chosen={recognitionChoice} daily={daily} />)
           : null
       }
       <Divider sx={{ margin: 2 }} />
@@ -383,19 +843,18 @@ function Game() {
 
 function App() {
   const [mounted, setMounted] = useState(false)
-  const [ready, setReady] = useState(false)
+  const [game, setGame] = useState<GameHandle | null>(null)
 
   if (!mounted) {
     setMounted(true)
     game_wasm().then(() => {
       init_panic_hook()
-      init_game()
-      setReady(true)
+      setGame(new GameHandle())
     })
   }
 
   return <Paper sx={{ padding: 2 }}>
-    {ready ? <Game /> : <p>Loading...</p>}
+    {game ? <Game game={game} /> : <p>Loading...</p>}
   </Paper>
 
 }
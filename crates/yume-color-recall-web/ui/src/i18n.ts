@@ -17,12 +17,75 @@ const resources = {
             'target_color': 'Target Color',
             'your_color': 'Your Color',
             'lower_is_better': 'lower is better',
+            'other_metrics': 'All Metrics',
+            'adaptive_delay': 'Adaptive delay: the wait gets longer as you get better',
+            'adaptive_threshold': 'You keep a color within ΔE 5 most of the time over a delay of about ',
+            'mode': 'Mode',
+            'metric': 'Scoring metric',
+            'mode_recall': 'Recall: pick the color with sliders',
+            'mode_recognition': 'Recognition: pick the color among 4, others at ΔE ',
+            'step_3_recognition_instructions': 'Which of these is the color you saw?',
+            'recognition_correct': 'Correct!',
+            'recognition_wrong': 'Not quite, the color you saw was option ',
+            'recognition_accuracy': 'Recognition accuracy',
+            'recognition_threshold': 'You tell colors apart most of the time from about ΔE ',
+            'cvd': 'Color vision deficiency simulation',
+            'cvd_none': 'Off',
+            'cvd_protan': 'Protan (red)',
+            'cvd_deutan': 'Deutan (green)',
+            'cvd_tritan': 'Tritan (blue)',
+            'cvd_severity': 'Severity: ',
+            'cvd_mode_display': 'Show colors as seen',
+            'cvd_mode_scoring': 'Score as seen',
+            'invalid_hex': 'Expected #rgb or #rrggbb',
+            'css_export': 'In CSS',
+            'css_hex': 'Hex',
+            'css_rgb': 'rgb()',
+            'css_hsl': 'hsl()',
+            'css_hwb': 'hwb()',
+            'css_lab': 'lab()',
+            'css_lch': 'lch()',
+            'css_oklab': 'oklab()',
+            'css_oklch': 'oklch()',
+            'css_display_p3': 'Display P3',
+            'practice': 'Practice Your Own Colors',
+            'practice_color': 'Any CSS color',
+            'practice_color_start': 'Practice this color',
+            'practice_palette': 'Palette (CSS variables or JSON)',
+            'practice_palette_use': 'Draw from palette',
+            'practice_palette_clear': 'Back to random colors',
+            'practice_excluded': 'Hard to recall: ',
+            'out_of_gamut': 'This color is outside of what your screen can show, the closest displayable color is shown and scored instead.',
+            'exclusion_policy': 'Color range of the next round',
+            'exclusion_default': 'Default',
+            'exclusion_strict': 'Strict',
+            'exclusion_none': 'Hard (all colors)',
+            'exclusion_display_robust': 'Display Robust',
+            'btn_export_json': 'Export JSON',
+            'btn_export_csv': 'Export CSV',
+            'session_stats': 'This Session',
+            'session_mean': 'Mean',
+            'session_median': 'Median',
+            'session_best': 'Best',
+            'session_worst': 'Worst',
+            'feedback_too_dark': 'Your color was too dark.',
+            'feedback_too_light': 'Your color was too light.',
+            'feedback_lightness_ok': 'Lightness was right.',
+            'feedback_too_dull': 'Your color was too desaturated.',
+            'feedback_too_vivid': 'Your color was too saturated.',
+            'feedback_chroma_ok': 'Saturation was right.',
+            'feedback_hue_off': 'Hue was off.',
+            'feedback_hue_ok': 'Hue was right.',
+            'feedback_breakdown': 'CIEDE2000 breakdown',
             'unacceptable_warning': 'The current color will never be tested: ',
             'last_model': 'Last Color Space Used: ',
             'time_taken_memory': 'Time Taken to Memorize (unscored)',
             'time_taken_recall': 'Time Taken to Recall (unscored)',
             'btn_next': 'Next',
             'btn_reset': 'Reset',
+            'daily_start': 'Play today\'s daily challenge',
+            'daily_challenge': 'Daily challenge',
+            'challenge_seed': 'Seed of this color: ',
             'calibration': 'Calibration',
             'calibration_toggle': 'Show/Hide Calibration Helper',
             'calibration_instructions': 'For best results please disable auto brightness and adjust your screen brightness to maximize contrast.',
@@ -58,12 +121,75 @@ const resources = {
             'target_color': '目标颜色',
             'your_color': '您选择的颜色',
             'lower_is_better': '分数越低越好',
+            'other_metrics': '所有色差公式',
+            'adaptive_delay': '自适应等待：表现越好等待时间越长',
+            'adaptive_threshold': '在以下等待时间内，你大多能把颜色误差控制在 ΔE 5 以内：',
+            'mode': '模式',
+            'metric': '评分的色差公式',
+            'mode_recall': '回忆：用滑块调出颜色',
+            'mode_recognition': '辨认：从 4 个颜色中选出，其余颜色相差 ΔE ',
+            'step_3_recognition_instructions': '哪一个是你看到的颜色？',
+            'recognition_correct': '正确！',
+            'recognition_wrong': '不对，你看到的颜色是选项 ',
+            'recognition_accuracy': '辨认正确率',
+            'recognition_threshold': '你大多能分辨出的最小色差约为 ΔE ',
+            'cvd': '色觉异常模拟',
+            'cvd_none': '关闭',
+            'cvd_protan': '红色弱/红色盲',
+            'cvd_deutan': '绿色弱/绿色盲',
+            'cvd_tritan': '蓝色弱/蓝色盲',
+            'cvd_severity': '程度：',
+            'cvd_mode_display': '按模拟效果显示颜色',
+            'cvd_mode_scoring': '按模拟效果计分',
+            'invalid_hex': '请输入 #rgb 或 #rrggbb',
+            'css_export': 'CSS 写法',
+            'css_hex': '十六进制',
+            'css_rgb': 'rgb()',
+            'css_hsl': 'hsl()',
+            'css_hwb': 'hwb()',
+            'css_lab': 'lab()',
+            'css_lch': 'lch()',
+            'css_oklab': 'oklab()',
+            'css_oklch': 'oklch()',
+            'css_display_p3': 'Display P3',
+            'practice': '练习自定义颜色',
+            'practice_color': '任意 CSS 颜色',
+            'practice_color_start': '练习这个颜色',
+            'practice_palette': '调色板（CSS 变量或 JSON）',
+            'practice_palette_use': '从调色板抽取',
+            'practice_palette_clear': '恢复随机颜色',
+            'practice_excluded': '较难记忆：',
+            'out_of_gamut': '该颜色超出了屏幕可显示的范围，显示和计分将使用最接近的可显示颜色。',
+            'exclusion_policy': '下一轮的颜色范围',
+            'exclusion_default': '默认',
+            'exclusion_strict': '严格',
+            'exclusion_none': '困难（所有颜色）',
+            'exclusion_display_robust': '显示器无关',
+            'btn_export_json': '导出JSON',
+            'btn_export_csv': '导出CSV',
+            'session_stats': '本次统计',
+            'session_mean': '平均',
+            'session_median': '中位数',
+            'session_best': '最好',
+            'session_worst': '最差',
+            'feedback_too_dark': '你选的颜色太暗了。',
+            'feedback_too_light': '你选的颜色太亮了。',
+            'feedback_lightness_ok': '亮度正确。',
+            'feedback_too_dull': '你选的颜色饱和度太低了。',
+            'feedback_too_vivid': '你选的颜色饱和度太高了。',
+            'feedback_chroma_ok': '饱和度正确。',
+            'feedback_hue_off': '色相有偏差。',
+            'feedback_hue_ok': '色相正确。',
+            'feedback_breakdown': 'CIEDE2000 分解',
             'unacceptable_warning': '当前颜色不会被测试： ',
             'last_model': '上次使用的颜色空间： ',
             'time_taken_memory': '记忆所用时间（不计分）',
             'time_taken_recall': '回忆所用时间（不计分）',
             'btn_next': '下一步',
             'btn_reset': '重置',
+            'daily_start': '挑战今天的每日题目',
+            'daily_challenge': '每日挑战',
+            'challenge_seed': '本题颜色的种子：',
             'calibration': '校准',
             'calibration_toggle': '显示/隐藏校准助手',
             'calibration_instructions': '为了获得最佳效果，请关闭自动亮度并调整屏幕亮度以最大化对比度。',
@@ -98,12 +224,75 @@ const resources = {
             'target_color': '目標色',
             'your_color': 'あなたの色',
             'lower_is_better': '低いほどよい',
+            'other_metrics': 'すべての色差式',
+            'adaptive_delay': '適応ディレイ：上達するほど待ち時間が長くなります',
+            'adaptive_threshold': 'ほとんどの場合 ΔE 5 以内で色を覚えていられる待ち時間：',
+            'mode': 'モード',
+            'metric': '採点の色差式',
+            'mode_recall': '再生：スライダーで色を作る',
+            'mode_recognition': '再認：4 色から選ぶ、他の色との差 ΔE ',
+            'step_3_recognition_instructions': '見た色はどれですか？',
+            'recognition_correct': '正解！',
+            'recognition_wrong': '残念、見た色は次の番号でした：',
+            'recognition_accuracy': '再認の正答率',
+            'recognition_threshold': 'ほとんどの場合見分けられる色差：ΔE ',
+            'cvd': '色覚異常シミュレーション',
+            'cvd_none': 'オフ',
+            'cvd_protan': 'P型（赤）',
+            'cvd_deutan': 'D型（緑）',
+            'cvd_tritan': 'T型（青）',
+            'cvd_severity': '程度：',
+            'cvd_mode_display': '見え方どおりに色を表示',
+            'cvd_mode_scoring': '見え方どおりに採点',
+            'invalid_hex': '#rgb または #rrggbb の形式で入力してください',
+            'css_export': 'CSS 表記',
+            'css_hex': '16進数',
+            'css_rgb': 'rgb()',
+            'css_hsl': 'hsl()',
+            'css_hwb': 'hwb()',
+            'css_lab': 'lab()',
+            'css_lch': 'lch()',
+            'css_oklab': 'oklab()',
+            'css_oklch': 'oklch()',
+            'css_display_p3': 'Display P3',
+            'practice': '自分の色で練習',
+            'practice_color': '任意の CSS カラー',
+            'practice_color_start': 'この色で練習',
+            'practice_palette': 'パレット（CSS 変数または JSON）',
+            'practice_palette_use': 'パレットから出題',
+            'practice_palette_clear': 'ランダムな色に戻す',
+            'practice_excluded': '記憶しにくい色：',
+            'out_of_gamut': 'この色は画面で表示できる範囲外です。表示と採点には最も近い表示可能な色が使われます。',
+            'exclusion_policy': '次のラウンドの色の範囲',
+            'exclusion_default': '標準',
+            'exclusion_strict': '厳しめ',
+            'exclusion_none': 'ハード（すべての色）',
+            'exclusion_display_robust': 'ディスプレイ差に強い',
+            'btn_export_json': 'JSONで書き出す',
+            'btn_export_csv': 'CSVで書き出す',
+            'session_stats': '今回のセッション',
+            'session_mean': '平均',
+            'session_median': '中央値',
+            'session_best': '最高',
+            'session_worst': '最低',
+            'feedback_too_dark': '選んだ色が暗すぎます。',
+            'feedback_too_light': '選んだ色が明るすぎます。',
+            'feedback_lightness_ok': '明度は合っています。',
+            'feedback_too_dull': '選んだ色の彩度が低すぎます。',
+            'feedback_too_vivid': '選んだ色の彩度が高すぎます。',
+            'feedback_chroma_ok': '彩度は合っています。',
+            'feedback_hue_off': '色相がずれています。',
+            'feedback_hue_ok': '色相は合っています。',
+            'feedback_breakdown': 'CIEDE2000 の内訳',
             'unacceptable_warning': '現在の色はテストされません： ',
             'last_model': '最後に使用した色空間： ',
             'time_taken_memory': '記憶にかかった時間（スコア対象外）',
             'time_taken_recall': 'リコールにかかった時間（スコア対象外）',
             'btn_next': '次へ',
             'btn_reset': 'リセット',
+            'daily_start': '今日のデイリーチャレンジに挑戦',
+            'daily_challenge': 'デイリーチャレンジ',
+            'challenge_seed': 'この色のシード：',
             'calibration': 'キャリブレーション',
             'calibration_toggle': 'キャリブレーションヘルパーを表示/非表示',
             'calibration_instructions': '最良の結果を得るために、自動輝度を無効にして画面輝度を調整して、コントラストを最大化してください。',
@@ -0,0 +1,247 @@
+use std::{fmt::Display, time::Duration};
+
+use crate::{
+    game::ColorChallenge,
+    metric::Ciede2000,
+    phase::{Clock, GameRound},
+    session::RoundResult,
+};
+
+// the first reversals mostly reflect the starting level, not the player
+const DISCARDED_REVERSALS: usize = 2;
+const COUNTED_REVERSALS: usize = 6;
+
+/// Parameters of a [`Staircase`]. Higher levels are harder.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct StaircaseConfig {
+    pub start: f32,
+    pub min: f32,
+    pub max: f32,
+    /// Multiplicative step, the level is multiplied or divided by this, must be above 1.
+    pub step_factor: f32,
+    /// Rounds scoring at or below this are correct.
+    pub correct_within: f32,
+    /// Consecutive correct rounds needed before the level goes up.
+    pub correct_to_advance: usize,
+}
+
+impl StaircaseConfig {
+    /// Delay in seconds, starting easy and allowing up to a minute, correct within a CIEDE2000
+    /// difference of 5.
+    pub const DELAY: Self = StaircaseConfig {
+        start: 2.0,
+        min: 0.5,
+        max: 60.0,
+        step_factor: 1.5,
+        correct_within: 5.0,
+        correct_to_advance: 2,
+    };
+}
+
+impl StaircaseConfig {
+    /// Check that levels stay positive and finite and that the step moves them.
+    pub fn validate(&self) -> Result<(), StaircaseError> {
+        let invalid = |field| Err(StaircaseError { field });
+        if !self.start.is_finite() {
+            return invalid("start");
+        }
+        if !(self.min.is_finite() && self.min > 0.0) {
+            return invalid("min");
+        }
+        if !(self.max.is_finite() && self.max >= self.min) {
+            return invalid("max");
+        }
+        if !(self.step_factor.is_finite() && self.step_factor > 1.0) {
+            return invalid("step_factor");
+        }
+        if self.correct_within.is_nan() {
+            return invalid("correct_within");
+        }
+        if self.correct_to_advance == 0 {
+            return invalid("correct_to_advance");
+        }
+        Ok(())
+    }
+}
+
+/// A [`StaircaseConfig`] field is out of range.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct StaircaseError {
+    pub field: &'static str,
+}
+
+impl Display for StaircaseError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "invalid staircase {}", self.field)
+    }
+}
+
+impl std::error::Error for StaircaseError {}
+
+impl Default for StaircaseConfig {
+    fn default() -> Self {
+        Self::DELAY
+    }
+}
+
+/// One round as seen by the staircase.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Trial {
+    pub level: f32,
+    pub score: f32,
+    pub correct: bool,
+}
+
+/// A 1-up/n-down staircase, by default 2-down which converges to the level where 70.7% of
+/// rounds are correct.
+///
+/// See Levitt, H. (1971). Transformed up-down methods in psychoacoustics.
+#[derive(Debug, Clone)]
+pub struct Staircase {
+    config: StaircaseConfig,
+    level: f32,
+    streak: usize,
+    last_step_up: Option<bool>,
+    reversals: Vec<f32>,
+    trials: Vec<Trial>,
+}
+
+impl Staircase {
+    pub fn new(config: StaircaseConfig) -> Result<Self, StaircaseError> {
+        config.validate()?;
+        Ok(Self::unchecked(config))
+    }
+
+    // `clamp` panics unless `config` was validated
+    fn unchecked(config: StaircaseConfig) -> Self {
+        Staircase {
+            config,
+            level: config.start.clamp(config.min, config.max),
+            streak: 0,
+            last_step_up: None,
+            reversals: Vec::new(),
+            trials: Vec::new(),
+        }
+    }
+
+    pub fn config(&self) -> &StaircaseConfig {
+        &self.config
+    }
+
+    /// The level the next round should be played at.
+    pub fn level(&self) -> f32 {
+        self.level
+    }
+
+    pub fn trials(&self) -> &[Trial] {
+        &self.trials
+    }
+
+    /// Levels at which the staircase changed direction.
+    pub fn reversals(&self) -> &[f32] {
+        &self.reversals
+    }
+
+    /// Record a round played at the current level, returning the level of the next round.
+    pub fn record(&mut self, score: f32) -> f32 {
+        let correct = score <= self.config.correct_within;
+        self.trials.push(Trial {
+            level: self.level,
+            score,
+            correct,
+        });
+
+        let step_up = if correct {
+            self.streak += 1;
+            if self.streak < self.config.correct_to_advance {
+                return self.level;
+            }
+            true
+        } else {
+            false
+        };
+        self.streak = 0;
+
+        if self.last_step_up.is_some_and(|last| last != step_up) {
+            self.reversals.push(self.level);
+        }
+        self.last_step_up = Some(step_up);
+
+        let level = if step_up {
+            self.level * self.config.step_factor
+        } else {
+            self.level / self.config.step_factor
+        };
+        self.level = level.clamp(self.config.min, self.config.max);
+        self.level
+    }
+
+    /// The estimated threshold, the geometric mean of the recent reversals.
+    ///
+    /// `None` until there are enough reversals to say anything.
+    pub fn threshold(&self) -> Option<f32> {
+        let counted = self.reversals.get(DISCARDED_REVERSALS..)?;
+        let counted = &counted[counted.len().saturating_sub(COUNTED_REVERSALS)..];
+        if counted.len() < 2 {
+            return None;
+        }
+
+        let log_mean = counted.iter().map(|l| l.ln()).sum::<f32>() / counted.len() as f32;
+        Some(log_mean.exp())
+    }
+}
+
+/// Adapts the delay between memorizing and recalling to the player.
+///
+/// The threshold is the delay over which the player keeps a color within
+/// [`StaircaseConfig::correct_within`] most of the time, a skill number that does not depend on
+/// a single lucky round.
+#[derive(Debug, Clone)]
+pub struct AdaptiveDelay {
+    staircase: Staircase,
+}
+
+impl Default for AdaptiveDelay {
+    fn default() -> Self {
+        AdaptiveDelay {
+            staircase: Staircase::unchecked(StaircaseConfig::DELAY),
+        }
+    }
+}
+
+impl AdaptiveDelay {
+    /// `config` levels are in seconds, `max` must fit in a [`Duration`].
+    pub fn new(config: StaircaseConfig) -> Result<Self, StaircaseError> {
+        let staircase = Staircase::new(config)?;
+        if Duration::try_from_secs_f32(config.max).is_err() {
+            return Err(StaircaseError { field: "max" });
+        }
+        Ok(AdaptiveDelay { staircase })
+    }
+
+    pub fn staircase(&self) -> &Staircase {
+        &self.staircase
+    }
+
+    pub fn delay(&self) -> Duration {
+        Duration::from_secs_f32(self.staircase.level())
+    }
+
+    /// A round of `challenge` played with the current delay.
+    pub fn start_round<C: Clock>(&self, challenge: ColorChallenge, clock: C) -> GameRound<C> {
+        GameRound::with_delay(challenge, clock, self.delay())
+    }
+
+    /// Record a round played with the current delay, returning the delay for the next one.
+    ///
+    /// Rounds are judged by their CIEDE2000 difference whatever metric they were scored with, so
+    /// [`StaircaseConfig::correct_within`] is a ΔE00 while the levels stay in seconds.
+    pub fn record(&mut self, result: &RoundResult) -> Duration {
+        self.staircase.record(result.score_with(&Ciede2000));
+        self.delay()
+    }
+
+    pub fn threshold(&self) -> Option<Duration> {
+        self.staircase.threshold().map(Duration::from_secs_f32)
+    }
+}
@@ -0,0 +1,494 @@
+use std::fmt::Display;
+
+use palette::{
+    chromatic_adaptation::AdaptInto,
+    convert::{FromColorUnclamped, IntoColorUnclamped},
+    white_point::{D50, D65},
+    Hsl, Hsv, Hwb, Lab, LinSrgb, Oklab, Oklch, Srgb, Xyz,
+};
+
+use crate::{
+    game::{format_hex, hwb_to_srgb, parse_hex},
+    gamut::clip,
+};
+
+// linear sRGB <-> linear Display P3, from the CSS Color 4 sample code
+const P3_TO_SRGB: [[f32; 3]; 3] = [
+    [1.224_940_2, -0.224_940_4, 0.0],
+    [-0.042_056_955, 1.042_057_1, 0.0],
+    [-0.019_637_555, -0.078_636_05, 1.098_273_6],
+];
+const SRGB_TO_P3: [[f32; 3]; 3] = [
+    [0.822_462_1, 0.177_538, 0.0],
+    [0.033_194_2, 0.966_805_8, 0.0],
+    [0.017_082_632, 0.072_397_44, 0.910_519_9],
+];
+
+const FUNCTIONS: [&str; 10] = [
+    "rgb", "rgba", "hsl", "hsla", "hwb", "lab", "lch", "oklab", "oklch", "color",
+];
+
+// reference ranges of percentages, 100% is this much
+const LAB_AB_PERCENT: f32 = 125.0;
+const LCH_CHROMA_PERCENT: f32 = 150.0;
+const OKLAB_AB_PERCENT: f32 = 0.4;
+const OKLCH_CHROMA_PERCENT: f32 = 0.4;
+
+/// The CSS Color 4 syntaxes a color can be written in.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum CssFormat {
+    /// `#rrggbb`
+    #[default]
+    Hex,
+    /// `rgb(r g b)` with 0-255 channels
+    Rgb,
+    /// `hsl(h s% l%)`
+    Hsl,
+    /// `hwb(h w% b%)`
+    Hwb,
+    /// `lab(l a b)`, CIELAB relative to D50 as CSS defines it
+    Lab,
+    /// `lch(l c h)`, relative to D50
+    Lch,
+    /// `oklab(l a b)`
+    Oklab,
+    /// `oklch(l c h)`
+    Oklch,
+    /// `color(display-p3 r g b)`
+    DisplayP3,
+}
+
+impl CssFormat {
+    pub const ALL: [CssFormat; 9] = [
+        CssFormat::Hex,
+        CssFormat::Rgb,
+        CssFormat::Hsl,
+        CssFormat::Hwb,
+        CssFormat::Lab,
+        CssFormat::Lch,
+        CssFormat::Oklab,
+        CssFormat::Oklch,
+        CssFormat::DisplayP3,
+    ];
+
+    pub fn key(&self) -> &'static str {
+        match self {
+            CssFormat::Hex => "hex",
+            CssFormat::Rgb => "rgb",
+            CssFormat::Hsl => "hsl",
+            CssFormat::Hwb => "hwb",
+            CssFormat::Lab => "lab",
+            CssFormat::Lch => "lch",
+            CssFormat::Oklab => "oklab",
+            CssFormat::Oklch => "oklch",
+            CssFormat::DisplayP3 => "display_p3",
+        }
+    }
+
+    pub fn from_key(key: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|f| f.key() == key)
+    }
+
+    /// Whether colors outside the sRGB gamut can be written in this format, others are clipped.
+    pub fn is_wide_gamut(&self) -> bool {
+        !matches!(
+            self,
+            CssFormat::Hex | CssFormat::Rgb | CssFormat::Hsl | CssFormat::Hwb
+        )
+    }
+}
+
+impl Display for CssFormat {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.key())
+    }
+}
+
+/// A string that is not a supported CSS color.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum CssColorError {
+    InvalidHex(String),
+    UnknownFunction(String),
+    UnknownColorSpace(String),
+    ComponentCount {
+        function: String,
+        expected: usize,
+        got: usize,
+    },
+    InvalidComponent(String),
+    Syntax(String),
+}
+
+impl Display for CssColorError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            CssColorError::InvalidHex(hex) => write!(f, "invalid hex color {:?}", hex),
+            CssColorError::UnknownFunction(function) => {
+                write!(f, "unknown color function {:?}", function)
+            }
+            CssColorError::UnknownColorSpace(space) => {
+                write!(f, "unknown color space {:?}", space)
+            }
+            CssColorError::ComponentCount {
+                function,
+                expected,
+                got,
+            } => write!(
+                f,
+                "{}() takes {} components but {} were given",
+                function, expected, got
+            ),
+            CssColorError::InvalidComponent(component) => {
+                write!(f, "invalid color component {:?}", component)
+            }
+            CssColorError::Syntax(input) => write!(f, "not a CSS color: {:?}", input),
+        }
+    }
+}
+
+impl std::error::Error for CssColorError {}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Component {
+    Number(f32),
+    Percent(f32),
+    /// In degrees.
+    Angle(f32),
+}
+
+impl Component {
+    fn parse(token: &str) -> Result<Self, CssColorError> {
+        let invalid = || CssColorError::InvalidComponent(token.to_string());
+        let number = |s: &str| -> Result<f32, CssColorError> {
+            s.parse::<f32>()
+                .ok()
+                .filter(|v| v.is_finite())
+                .ok_or_else(invalid)
+        };
+
+        if token == "none" {
+            return Ok(Component::Number(0.0));
+        }
+        if let Some(value) = token.strip_suffix('%') {
+            return Ok(Component::Percent(number(value)?));
+        }
+        // longest suffixes first, "grad" ends in "rad"
+        for (unit, degrees) in [("grad", 0.9), ("turn", 360.0), ("deg", 1.0)] {
+            if let Some(value) = token.strip_suffix(unit) {
+                return Ok(Component::Angle(number(value)? * degrees));
+            }
+        }
+        if let Some(value) = token.strip_suffix("rad") {
+            return Ok(Component::Angle(number(value)?.to_degrees()));
+        }
+        Ok(Component::Number(number(token)?))
+    }
+
+    /// A number, or a percentage of `reference`.
+    fn scaled(self, reference: f32, token: &str) -> Result<f32, CssColorError> {
+        match self {
+            Component::Number(value) => Ok(value),
+            Component::Percent(value) => Ok(value / 100.0 * reference),
+            Component::Angle(_) => Err(CssColorError::InvalidComponent(token.to_string())),
+        }
+    }
+
+    fn hue(self, token: &str) -> Result<f32, CssColorError> {
+        match self {
+            Component::Number(degrees) | Component::Angle(degrees) => Ok(degrees),
+            Component::Percent(_) => Err(CssColorError::InvalidComponent(token.to_string())),
+        }
+    }
+}
+
+/// Parse a CSS Color 4 color: a hex code, `rgb()`, `hsl()`, `hwb()`, `lab()`, `lch()`,
+/// `oklab()`, `oklch()` or `color()` in `srgb`, `srgb-linear` or `display-p3`.
+///
+/// Alpha is accepted and ignored. Wide gamut colors are returned unclamped, so the result may lie
+/// outside the sRGB gamut.
+pub fn parse_css_color(input: &str) -> Result<Srgb, CssColorError> {
+    let css = input.trim().to_ascii_lowercase();
+    if css.starts_with('#') {
+        return parse_hex_with_alpha(&css)
+            .ok_or_else(|| CssColorError::InvalidHex(input.trim().to_string()));
+    }
+
+    let syntax = || CssColorError::Syntax(input.trim().to_string());
+    let (function, rest) = css.split_once('(').ok_or_else(syntax)?;
+    let arguments = rest.strip_suffix(')').ok_or_else(syntax)?;
+    let function = function.trim();
+    if !FUNCTIONS.contains(&function) {
+        return Err(CssColorError::UnknownFunction(function.to_string()));
+    }
+
+    // modern `a b c / alpha` or legacy `a, b, c, alpha`
+    let (channels, legacy) = match arguments.split_once('/') {
+        Some((channels, alpha)) => {
+            Component::parse(alpha.trim())?;
+            (channels, false)
+        }
+        None => (arguments, arguments.contains(',')),
+    };
+    let mut tokens: Vec<&str> = if legacy {
+        channels.split(',').map(str::trim).collect()
+    } else {
+        channels.split_whitespace().collect()
+    };
+
+    let space = if function == "color" {
+        if tokens.is_empty() {
+            return Err(syntax());
+        }
+        Some(tokens.remove(0))
+    } else {
+        None
+    };
+
+    // legacy syntax puts alpha in a fourth component
+    let with_alpha = matches!(function, "rgb" | "rgba" | "hsl" | "hsla");
+    if legacy && with_alpha && tokens.len() == 4 {
+        Component::parse(tokens.pop().unwrap())?;
+    }
+    if tokens.len() != 3 {
+        return Err(CssColorError::ComponentCount {
+            function: function.to_string(),
+            expected: 3,
+            got: tokens.len(),
+        });
+    }
+
+    let components = tokens
+        .iter()
+        .map(|t| Component::parse(t))
+        .collect::<Result<Vec<_>, _>>()?;
+    let [a, b, c] = [components[0], components[1], components[2]];
+    let [ta, tb, tc] = [tokens[0], tokens[1], tokens[2]];
+
+    match function {
+        "rgb" | "rgba" => Ok(Srgb::new(
+            a.scaled(255.0, ta)? / 255.0,
+            b.scaled(255.0, tb)? / 255.0,
+            c.scaled(255.0, tc)? / 255.0,
+        )),
+        "hsl" | "hsla" => {
+            let hsl = Hsl::new(
+                a.hue(ta)?,
+                b.scaled(100.0, tb)? / 100.0,
+                c.scaled(100.0, tc)? / 100.0,
+            );
+            Ok(hsl.into_color_unclamped())
+        }
+        "hwb" => Ok(hwb_to_srgb(
+            a.hue(ta)?,
+            b.scaled(100.0, tb)? / 100.0,
+            c.scaled(100.0, tc)? / 100.0,
+        )),
+        "lab" => Ok(lab_d50_to_srgb(Lab::new(
+            a.scaled(100.0, ta)?,
+            b.scaled(LAB_AB_PERCENT, tb)?,
+            c.scaled(LAB_AB_PERCENT, tc)?,
+        ))),
+        "lch" => {
+            let chroma = b.scaled(LCH_CHROMA_PERCENT, tb)?.max(0.0);
+            let hue = c.hue(tc)?.to_radians();
+            Ok(lab_d50_to_srgb(Lab::new(
+                a.scaled(100.0, ta)?,
+                chroma * hue.cos(),
+                chroma * hue.sin(),
+            )))
+        }
+        "oklab" => Ok(oklab_to_srgb(Oklab::new(
+            a.scaled(1.0, ta)?,
+            b.scaled(OKLAB_AB_PERCENT, tb)?,
+            c.scaled(OKLAB_AB_PERCENT, tc)?,
+        ))),
+        "oklch" => {
+            let oklch = Oklch::new(
+                a.scaled(1.0, ta)?,
+                b.scaled(OKLCH_CHROMA_PERCENT, tb)?.max(0.0),
+                c.hue(tc)?,
+            );
+            Ok(oklab_to_srgb(oklch.into_color_unclamped()))
+        }
+        "color" => {
+            let rgb = [a.scaled(1.0, ta)?, b.scaled(1.0, tb)?, c.scaled(1.0, tc)?];
+            match space.unwrap_or_default() {
+                "srgb" => Ok(Srgb::new(rgb[0], rgb[1], rgb[2])),
+                "srgb-linear" => Ok(encode(LinSrgb::new(rgb[0], rgb[1], rgb[2]))),
+                "display-p3" => {
+                    let linear = rgb.map(to_linear);
+                    let srgb = multiply(&P3_TO_SRGB, linear);
+                    Ok(encode(LinSrgb::new(srgb[0], srgb[1], srgb[2])))
+                }
+                other => Err(CssColorError::UnknownColorSpace(other.to_string())),
+            }
+        }
+        other => Err(CssColorError::UnknownFunction(other.to_string())),
+    }
+}
+
+/// Write `color` in `format`, clipped to the sRGB gamut unless the format is
+/// [wide gamut](CssFormat::is_wide_gamut).
+///
+/// Channels are rounded to the nearest representable value, not truncated.
+pub fn to_css(color: Srgb, format: CssFormat) -> String {
+    let clipped = clip(color);
+    match format {
+        CssFormat::Hex => format_hex(clipped),
+        CssFormat::Rgb => {
+            let rgb: Srgb<u8> = clipped.into_format();
+            format!("rgb({} {} {})", rgb.red, rgb.green, rgb.blue)
+        }
+        CssFormat::Hsl => {
+            let hsl: Hsl = clipped.into_color_unclamped();
+            format!(
+                "hsl({} {}% {}%)",
+                number(hsl.hue.into_positive_degrees(), 2),
+                number(hsl.saturation * 100.0, 2),
+                number(hsl.lightness * 100.0, 2)
+            )
+        }
+        CssFormat::Hwb => {
+            let hsv: Hsv = clipped.into_color_unclamped();
+            let hwb: Hwb = hsv.into_color_unclamped();
+            format!(
+                "hwb({} {}% {}%)",
+                number(hwb.hue.into_positive_degrees(), 2),
+                number(hwb.whiteness * 100.0, 2),
+                number(hwb.blackness * 100.0, 2)
+            )
+        }
+        CssFormat::Lab => {
+            let lab = srgb_to_lab_d50(color);
+            format!(
+                "lab({} {} {})",
+                number(lab.l, 2),
+                number(lab.a, 2),
+                number(lab.b, 2)
+            )
+        }
+        CssFormat::Lch => {
+            let lab = srgb_to_lab_d50(color);
+            let chroma = (lab.a * lab.a + lab.b * lab.b).sqrt();
+            let hue = lab.b.atan2(lab.a).to_degrees().rem_euclid(360.0);
+            format!(
+                "lch({} {} {})",
+                number(lab.l, 2),
+                number(chroma, 2),
+                number(hue, 2)
+            )
+        }
+        CssFormat::Oklab => {
+            let oklab = Oklab::from_color_unclamped(linear(color));
+            format!(
+                "oklab({} {} {})",
+                number(oklab.l, 4),
+                number(oklab.a, 4),
+                number(oklab.b, 4)
+            )
+        }
+        CssFormat::Oklch => {
+            let oklch: Oklch = Oklab::from_color_unclamped(linear(color)).into_color_unclamped();
+            format!(
+                "oklch({} {} {})",
+                number(oklch.l, 4),
+                number(oklch.chroma, 4),
+                number(oklch.hue.into_positive_degrees(), 2)
+            )
+        }
+        CssFormat::DisplayP3 => {
+            let srgb = linear(color);
+            let p3 = multiply(&SRGB_TO_P3, [srgb.red, srgb.green, srgb.blue]).map(from_linear);
+            format!(
+                "color(display-p3 {} {} {})",
+                number(p3[0], 4),
+                number(p3[1], 4),
+                number(p3[2], 4)
+            )
+        }
+    }
+}
+
+fn parse_hex_with_alpha(hex: &str) -> Option<Srgb> {
+    let digits = hex.strip_prefix('#')?;
+    // checked first, the byte slicing below is only safe for ASCII
+    if !digits.chars().all(|c| c.is_ascii_hexdigit()) {
+        return None;
+    }
+    let rgb = match digits.len() {
+        4 => &digits[..3],
+        8 => &digits[..6],
+        _ => digits,
+    };
+    parse_hex(rgb).map(|srgb| srgb.into_format())
+}
+
+/// Rounded to `decimals` without trailing zeros.
+fn number(value: f32, decimals: usize) -> String {
+    let formatted = format!("{:.*}", decimals, value);
+    let trimmed = if formatted.contains('.') {
+        formatted.trim_end_matches('0').trim_end_matches('.')
+    } else {
+        &formatted
+    };
+    if trimmed == "-0" {
+        "0".to_string()
+    } else {
+        trimmed.to_string()
+    }
+}
+
+fn multiply(matrix: &[[f32; 3]; 3], v: [f32; 3]) -> [f32; 3] {
+    matrix.map(|row| row[0] * v[0] + row[1] * v[1] + row[2] * v[2])
+}
+
+// the sRGB transfer function, extended to negative values as CSS does
+fn to_linear(c: f32) -> f32 {
+    let abs = c.abs();
+    if abs <= 0.04045 {
+        c / 12.92
+    } else {
+        c.signum() * ((abs + 0.055) / 1.055).powf(2.4)
+    }
+}
+
+fn from_linear(c: f32) -> f32 {
+    let abs = c.abs();
+    if abs > 0.003_130_8 {
+        c.signum() * (1.055 * abs.powf(1.0 / 2.4) - 0.055)
+    } else {
+        12.92 * c
+    }
+}
+
+fn linear(color: Srgb) -> LinSrgb {
+    LinSrgb::new(
+        to_linear(color.red),
+        to_linear(color.green),
+        to_linear(color.blue),
+    )
+}
+
+fn encode(color: LinSrgb) -> Srgb {
+    Srgb::new(
+        from_linear(color.red),
+        from_linear(color.green),
+        from_linear(color.blue),
+    )
+}
+
+fn srgb_to_lab_d50(color: Srgb) -> Lab<D50> {
+    let xyz: Xyz<D65> = Xyz::from_color_unclamped(linear(color));
+    let xyz: Xyz<D50> = xyz.adapt_into();
+    Lab::from_color_unclamped(xyz)
+}
+
+fn lab_d50_to_srgb(lab: Lab<D50>) -> Srgb {
+    let xyz: Xyz<D50> = Xyz::from_color_unclamped(lab);
+    let xyz: Xyz<D65> = xyz.adapt_into();
+    encode(LinSrgb::from_color_unclamped(xyz))
+}
+
+fn oklab_to_srgb(oklab: Oklab) -> Srgb {
+    encode(LinSrgb::from_color_unclamped(oklab))
+}
@@ -0,0 +1,332 @@
+use std::fmt::Display;
+
+use palette::{convert::IntoColorUnclamped, IntoColor, Lab, LinSrgb, Srgb};
+
+use crate::{gamut::clip, metric::DifferenceMetric};
+
+type Matrix = [[f32; 3]; 3];
+
+const IDENTITY: Matrix = [[1.0, 0.0, 0.0], [0.0, 1.0, 0.0], [0.0, 0.0, 1.0]];
+
+// Machado, Oliveira & Fernandes (2009), linear RGB matrices for severities 0.0, 0.1, …, 1.0
+const PROTAN: [Matrix; 11] = [
+    IDENTITY,
+    [
+        [0.856167, 0.182038, -0.038205],
+        [0.029342, 0.955115, 0.015544],
+        [-0.002880, -0.001563, 1.004443],
+    ],
+    [
+        [0.734766, 0.334872, -0.069637],
+        [0.051840, 0.919198, 0.028963],
+        [-0.004928, -0.004209, 1.009137],
+    ],
+    [
+        [0.630323, 0.465641, -0.095964],
+        [0.069181, 0.890046, 0.040773],
+        [-0.006308, -0.007724, 1.014032],
+    ],
+    [
+        [0.539009, 0.579343, -0.118352],
+        [0.082546, 0.866121, 0.051332],
+        [-0.007136, -0.011959, 1.019095],
+    ],
+    [
+        [0.458064, 0.679578, -0.137642],
+        [0.092785, 0.846313, 0.060902],
+        [-0.007494, -0.016807, 1.024301],
+    ],
+    [
+        [0.385450, 0.769005, -0.154455],
+        [0.100526, 0.829802, 0.069673],
+        [-0.007442, -0.022190, 1.029632],
+    ],
+    [
+        [0.319627, 0.849633, -0.169261],
+        [0.106241, 0.815969, 0.077790],
+        [-0.007025, -0.028051, 1.035076],
+    ],
+    [
+        [0.259411, 0.923008, -0.182420],
+        [0.110296, 0.804340, 0.085364],
+        [-0.006276, -0.034346, 1.040622],
+    ],
+    [
+        [0.203876, 0.990338, -0.194214],
+        [0.112975, 0.794542, 0.092483],
+        [-0.005222, -0.041043, 1.046265],
+    ],
+    [
+        [0.152286, 1.052583, -0.204868],
+        [0.114503, 0.786281, 0.099216],
+        [-0.003882, -0.048116, 1.051998],
+    ],
+];
+
+const DEUTAN: [Matrix; 11] = [
+    IDENTITY,
+    [
+        [0.866435, 0.177704, -0.044139],
+        [0.049567, 0.939063, 0.011370],
+        [-0.003453, 0.007233, 0.996220],
+    ],
+    [
+        [0.760729, 0.319078, -0.079807],
+        [0.090568, 0.889315, 0.020117],
+        [-0.006027, 0.013325, 0.992702],
+    ],
+    [
+        [0.675425, 0.433850, -0.109275],
+        [0.125303, 0.847755, 0.026942],
+        [-0.007950, 0.018572, 0.989378],
+    ],
+    [
+        [0.605511, 0.528560, -0.134071],
+        [0.155318, 0.812366, 0.032316],
+        [-0.009376, 0.023176, 0.986200],
+    ],
+    [
+        [0.547494, 0.607765, -0.155259],
+        [0.181692, 0.781742, 0.036566],
+        [-0.010410, 0.027275, 0.983136],
+    ],
+    [
+        [0.498864, 0.674741, -0.173604],
+        [0.205199, 0.754872, 0.039929],
+        [-0.011131, 0.030969, 0.980162],
+    ],
+    [
+        [0.457771, 0.731899, -0.189670],
+        [0.226409, 0.731012, 0.042579],
+        [-0.011595, 0.034333, 0.977261],
+    ],
+    [
+        [0.422823, 0.781057, -0.203881],
+        [0.245752, 0.709602, 0.044646],
+        [-0.011843, 0.037423, 0.974421],
+    ],
+    [
+        [0.392952, 0.823610, -0.216562],
+        [0.263559, 0.690210, 0.046232],
+        [-0.011910, 0.040281, 0.971630],
+    ],
+    [
+        [0.367322, 0.860646, -0.227968],
+        [0.280085, 0.672501, 0.047413],
+        [-0.011820, 0.042940, 0.968881],
+    ],
+];
+
+const TRITAN: [Matrix; 11] = [
+    IDENTITY,
+    [
+        [0.926670, 0.092514, -0.019184],
+        [0.021191, 0.964503, 0.014306],
+        [0.008437, 0.054813, 0.936750],
+    ],
+    [
+        [0.895720, 0.133330, -0.029050],
+        [0.029997, 0.945400, 0.024603],
+        [0.013027, 0.104707, 0.882266],
+    ],
+    [
+        [0.905871, 0.127791, -0.033662],
+        [0.026856, 0.941251, 0.031893],
+        [0.013410, 0.148296, 0.838294],
+    ],
+    [
+        [0.948035, 0.089490, -0.037526],
+        [0.014364, 0.946792, 0.038844],
+        [0.010853, 0.193991, 0.795156],
+    ],
+    [
+        [1.017277, 0.027029, -0.044306],
+        [-0.006113, 0.958479, 0.047634],
+        [0.006379, 0.248708, 0.744913],
+    ],
+    [
+        [1.104996, -0.046633, -0.058363],
+        [-0.032137, 0.971635, 0.060503],
+        [0.001336, 0.317922, 0.680742],
+    ],
+    [
+        [1.193214, -0.109812, -0.083402],
+        [-0.058496, 0.979410, 0.079086],
+        [-0.002346, 0.403492, 0.598854],
+    ],
+    [
+        [1.257728, -0.139648, -0.118081],
+        [-0.078003, 0.975409, 0.102594],
+        [-0.003316, 0.501214, 0.502102],
+    ],
+    [
+        [1.278864, -0.125333, -0.153531],
+        [-0.084748, 0.957674, 0.127074],
+        [-0.000989, 0.601151, 0.399838],
+    ],
+    [
+        [1.255528, -0.076749, -0.178779],
+        [-0.078411, 0.930809, 0.147602],
+        [0.004733, 0.691367, 0.303900],
+    ],
+];
+
+/// The cone type whose response is shifted.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(rename_all = "snake_case")
+)]
+pub enum Deficiency {
+    /// Long wavelength (red) cones.
+    Protan,
+    /// Medium wavelength (green) cones.
+    Deutan,
+    /// Short wavelength (blue) cones.
+    Tritan,
+}
+
+impl Deficiency {
+    pub const ALL: [Deficiency; 3] = [Deficiency::Protan, Deficiency::Deutan, Deficiency::Tritan];
+
+    pub fn key(&self) -> &'static str {
+        match self {
+            Deficiency::Protan => "protan",
+            Deficiency::Deutan => "deutan",
+            Deficiency::Tritan => "tritan",
+        }
+    }
+
+    pub fn from_key(key: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|d| d.key() == key)
+    }
+
+    fn matrices(&self) -> &'static [Matrix; 11] {
+        match self {
+            Deficiency::Protan => &PROTAN,
+            Deficiency::Deutan => &DEUTAN,
+            Deficiency::Tritan => &TRITAN,
+        }
+    }
+}
+
+impl Display for Deficiency {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.key())
+    }
+}
+
+/// How colors appear to an observer with a color vision deficiency.
+///
+/// See Machado, G. M., Oliveira, M. M., & Fernandes, L. A. (2009). A physiologically-based model
+/// for simulation of color vision deficiency.
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct CvdSimulation {
+    pub deficiency: Deficiency,
+    /// From 0 for normal vision to 1 for dichromacy (protanopia, deuteranopia, tritanopia).
+    pub severity: f32,
+}
+
+impl CvdSimulation {
+    /// `severity` is clamped to 0–1.
+    pub fn new(deficiency: Deficiency, severity: f32) -> Self {
+        CvdSimulation {
+            deficiency,
+            severity: if severity.is_nan() {
+                0.0
+            } else {
+                severity.clamp(0.0, 1.0)
+            },
+        }
+    }
+
+    /// Full loss of one cone type.
+    pub fn dichromacy(deficiency: Deficiency) -> Self {
+        Self::new(deficiency, 1.0)
+    }
+
+    /// The linear RGB transform, interpolated between the tabulated severities.
+    pub fn matrix(&self) -> [[f32; 3]; 3] {
+        let matrices = self.deficiency.matrices();
+        let position = self.severity.clamp(0.0, 1.0) * 10.0;
+        let lower = (position.floor() as usize).min(9);
+        let t = position - lower as f32;
+
+        let mut matrix = [[0.0; 3]; 3];
+        for (row, out) in matrix.iter_mut().enumerate() {
+            for (col, value) in out.iter_mut().enumerate() {
+                let a = matrices[lower][row][col];
+                let b = matrices[lower + 1][row][col];
+                *value = a + t * (b - a);
+            }
+        }
+        matrix
+    }
+
+    /// The color as it appears to the simulated observer, clipped to the sRGB gamut.
+    pub fn simulate(&self, color: Srgb) -> Srgb {
+        let linear: LinSrgb = color.into_linear();
+        let m = self.matrix();
+        let apply =
+            |row: [f32; 3]| row[0] * linear.red + row[1] * linear.green + row[2] * linear.blue;
+        let simulated = LinSrgb::new(apply(m[0]), apply(m[1]), apply(m[2]));
+
+        clip(simulated.into_color_unclamped())
+    }
+}
+
+/// Which side of a round a [`CvdSimulation`] is applied to.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(rename_all = "snake_case")
+)]
+pub enum CvdMode {
+    /// Colors are shown as the observer would see them and scored as usual, practicing as the
+    /// observer.
+    #[default]
+    Display,
+    /// Colors are shown as usual and scored after simulation, so only differences the observer
+    /// can see count.
+    Scoring,
+}
+
+impl CvdMode {
+    pub const ALL: [CvdMode; 2] = [CvdMode::Display, CvdMode::Scoring];
+
+    pub fn key(&self) -> &'static str {
+        match self {
+            CvdMode::Display => "display",
+            CvdMode::Scoring => "scoring",
+        }
+    }
+
+    pub fn from_key(key: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|m| m.key() == key)
+    }
+}
+
+/// A metric applied to both colors after simulating a color vision deficiency.
+#[derive(Debug, Clone, Copy)]
+pub struct Simulated<M> {
+    pub simulation: CvdSimulation,
+    pub metric: M,
+}
+
+impl<M: DifferenceMetric> DifferenceMetric for Simulated<M> {
+    fn name(&self) -> &'static str {
+        self.metric.name()
+    }
+
+    fn difference(&self, reference: Lab, sample: Lab) -> f32 {
+        let simulate = |lab: Lab| -> Lab {
+            let srgb: Srgb = lab.into_color_unclamped();
+            self.simulation.simulate(srgb).into_color()
+        };
+        self.metric
+            .difference(simulate(reference), simulate(sample))
+    }
+}
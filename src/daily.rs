@@ -0,0 +1,115 @@
+use std::fmt::Display;
+
+use crate::game::{ChallengeSeed, ColorChallenge};
+
+/// Number of rounds in the default daily challenge.
+pub const DAILY_CHALLENGE_ROUNDS: usize = 5;
+
+// mixed into every daily seed so daily targets are unlikely to collide with plain numeric seeds,
+// the result is a hash so collisions are possible but improbable
+const DAILY_SALT: u64 = 0x7975_6d65_6461_696c;
+
+/// A calendar date in UTC, used to derive the daily challenge.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct UtcDate {
+    year: i32,
+    month: u8,
+    day: u8,
+}
+
+fn is_leap_year(year: i32) -> bool {
+    (year % 4 == 0 && year % 100 != 0) || year % 400 == 0
+}
+
+fn days_in_month(year: i32, month: u8) -> u8 {
+    match month {
+        1 | 3 | 5 | 7 | 8 | 10 | 12 => 31,
+        4 | 6 | 9 | 11 => 30,
+        2 if is_leap_year(year) => 29,
+        2 => 28,
+        _ => 0,
+    }
+}
+
+impl UtcDate {
+    pub fn new(year: i32, month: u8, day: u8) -> Option<Self> {
+        if day == 0 || day > days_in_month(year, month) {
+            return None;
+        }
+        Some(UtcDate { year, month, day })
+    }
+
+    pub fn year(&self) -> i32 {
+        self.year
+    }
+
+    pub fn month(&self) -> u8 {
+        self.month
+    }
+
+    pub fn day(&self) -> u8 {
+        self.day
+    }
+
+    /// Convert a count of days since 1970-01-01 into a date.
+    ///
+    /// See <https://howardhinnant.github.io/date_algorithms.html#civil_from_days>.
+    pub fn from_days_since_epoch(days: i64) -> Self {
+        let z = days + 719468;
+        let era = z.div_euclid(146097);
+        let doe = z.rem_euclid(146097);
+        let yoe = (doe - doe / 1460 + doe / 36524 - doe / 146096) / 365;
+        let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+        let mp = (5 * doy + 2) / 153;
+        let day = (doy - (153 * mp + 2) / 5 + 1) as u8;
+        let month = if mp < 10 { mp + 3 } else { mp - 9 } as u8;
+        let year = (yoe + era * 400 + if month <= 2 { 1 } else { 0 }) as i32;
+
+        UtcDate { year, month, day }
+    }
+
+    pub fn from_unix_seconds(secs: i64) -> Self {
+        Self::from_days_since_epoch(secs.div_euclid(86400))
+    }
+
+    /// Number of days since 1970-01-01.
+    ///
+    /// See <https://howardhinnant.github.io/date_algorithms.html#days_from_civil>.
+    pub fn days_since_epoch(&self) -> i64 {
+        let month = self.month as i64;
+        let day = self.day as i64;
+        let year = self.year as i64 - if month <= 2 { 1 } else { 0 };
+        let era = year.div_euclid(400);
+        let yoe = year.rem_euclid(400);
+        let doy = (153 * (if month > 2 { month - 3 } else { month + 9 }) + 2) / 5 + day - 1;
+        let doe = yoe * 365 + yoe / 4 - yoe / 100 + doy;
+
+        era * 146097 + doe - 719468
+    }
+}
+
+impl Display for UtcDate {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{:04}-{:02}-{:02}", self.year, self.month, self.day)
+    }
+}
+
+// SplitMix64 finalizer, spreads consecutive days and rounds over the whole seed space.
+fn mix(mut z: u64) -> u64 {
+    z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+    z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+    z ^ (z >> 31)
+}
+
+/// The seed of round `round` of the daily challenge for `date`.
+pub fn daily_seed(date: UtcDate, round: usize) -> ChallengeSeed {
+    let day = mix(DAILY_SALT ^ date.days_since_epoch() as u64);
+    ChallengeSeed(mix(day.wrapping_add(round as u64)))
+}
+
+/// The targets of the daily challenge for `date`, identical for everyone on that day.
+pub fn daily_challenges(date: UtcDate, count: usize) -> Vec<ColorChallenge> {
+    (0..count)
+        .map(|round| ColorChallenge::from_seed(daily_seed(date, round)))
+        .collect()
+}
@@ -0,0 +1,155 @@
+use palette::{Hsv, IntoColor, Lch, Srgb};
+
+use crate::game::ExcludeReason;
+
+/// Decides which colors are never used as targets, and warned about when picked.
+pub trait ExclusionPolicy {
+    fn check(&self, color: &Srgb) -> Option<ExcludeReason>;
+}
+
+/// Thresholds on the mean sRGB channel value and HSV saturation.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ChannelExclusion {
+    pub min_mean: f32,
+    pub max_mean: f32,
+    pub min_saturation: f32,
+    pub max_saturation: f32,
+}
+
+impl ChannelExclusion {
+    pub const DEFAULT: Self = ChannelExclusion {
+        min_mean: 0.08,
+        max_mean: 0.92,
+        min_saturation: 0.15,
+        max_saturation: 0.9,
+    };
+
+    pub const STRICT: Self = ChannelExclusion {
+        min_mean: 0.15,
+        max_mean: 0.85,
+        min_saturation: 0.25,
+        max_saturation: 0.85,
+    };
+}
+
+impl Default for ChannelExclusion {
+    fn default() -> Self {
+        Self::DEFAULT
+    }
+}
+
+impl ExclusionPolicy for ChannelExclusion {
+    fn check(&self, color: &Srgb) -> Option<ExcludeReason> {
+        let sum = color.blue + color.green + color.red;
+        if sum < self.min_mean * 3. {
+            return Some(ExcludeReason::TooDark);
+        }
+        if sum > self.max_mean * 3. {
+            return Some(ExcludeReason::TooBright);
+        }
+        let hsv: Hsv = (*color).into_color();
+        if hsv.saturation < self.min_saturation {
+            return Some(ExcludeReason::LowSaturation);
+        } else if hsv.saturation > self.max_saturation {
+            return Some(ExcludeReason::HighSaturation);
+        }
+        None
+    }
+}
+
+/// Thresholds on CIE L* and C*, which track how displays actually differ better than sRGB channels.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct LabExclusion {
+    pub min_lightness: f32,
+    pub max_lightness: f32,
+    pub min_chroma: f32,
+    pub max_chroma: f32,
+}
+
+impl LabExclusion {
+    /// Avoids the shadows and highlights that depend most on display brightness and calibration,
+    /// near-neutrals where white point differences dominate and extreme chroma that is clipped on
+    /// many panels.
+    pub const DISPLAY_ROBUST: Self = LabExclusion {
+        min_lightness: 25.0,
+        max_lightness: 85.0,
+        min_chroma: 15.0,
+        max_chroma: 80.0,
+    };
+}
+
+impl ExclusionPolicy for LabExclusion {
+    fn check(&self, color: &Srgb) -> Option<ExcludeReason> {
+        let lch: Lch = (*color).into_color();
+        if lch.l < self.min_lightness {
+            return Some(ExcludeReason::TooDark);
+        }
+        if lch.l > self.max_lightness {
+            return Some(ExcludeReason::TooBright);
+        }
+        if lch.chroma < self.min_chroma {
+            return Some(ExcludeReason::LowSaturation);
+        } else if lch.chroma > self.max_chroma {
+            return Some(ExcludeReason::HighSaturation);
+        }
+        None
+    }
+}
+
+/// Accepts every color, for a hard mode including dark and pastel colors.
+#[derive(Debug, Default, Clone, Copy)]
+pub struct NoExclusion;
+
+impl ExclusionPolicy for NoExclusion {
+    fn check(&self, _color: &Srgb) -> Option<ExcludeReason> {
+        None
+    }
+}
+
+/// The built-in policies, selectable by key.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(rename_all = "snake_case")
+)]
+pub enum ExclusionPreset {
+    #[default]
+    Default,
+    Strict,
+    None,
+    DisplayRobust,
+}
+
+impl ExclusionPreset {
+    pub const ALL: [ExclusionPreset; 4] = [
+        ExclusionPreset::Default,
+        ExclusionPreset::Strict,
+        ExclusionPreset::None,
+        ExclusionPreset::DisplayRobust,
+    ];
+
+    pub fn key(&self) -> &'static str {
+        match self {
+            ExclusionPreset::Default => "default",
+            ExclusionPreset::Strict => "strict",
+            ExclusionPreset::None => "none",
+            ExclusionPreset::DisplayRobust => "display_robust",
+        }
+    }
+
+    pub fn from_key(key: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|p| p.key() == key)
+    }
+}
+
+impl ExclusionPolicy for ExclusionPreset {
+    fn check(&self, color: &Srgb) -> Option<ExcludeReason> {
+        match self {
+            ExclusionPreset::Default => ChannelExclusion::DEFAULT.check(color),
+            ExclusionPreset::Strict => ChannelExclusion::STRICT.check(color),
+            ExclusionPreset::None => NoExclusion.check(color),
+            ExclusionPreset::DisplayRobust => LabExclusion::DISPLAY_ROBUST.check(color),
+        }
+    }
+}
@@ -1,10 +1,35 @@
-use std::fmt::Display;
+use std::{fmt::Display, num::ParseIntError, str::FromStr};
 
 use num_traits::{Num, NumCast};
-use palette::{color_difference::ImprovedCiede2000, Hsl, Hsv, IntoColor, Lab, Lch, Srgb, Xyz};
-use rand::Rng;
+use palette::{
+    convert::IntoColorUnclamped, Hsl, Hsluv, Hsv, Hwb, IntoColor, Lab, Lch, Lchuv, Luv, Oklab,
+    Oklch, Srgb, Xyz,
+};
+use rand::{Rng, SeedableRng};
+use rand_chacha::ChaCha8Rng;
+
+use crate::{
+    cvd::CvdSimulation,
+    exclusion::{ExclusionPolicy, ExclusionPreset},
+    gamut::{clip, is_in_gamut, GamutMapping},
+    metric::{Ciede2000, Ciede2000Breakdown, DifferenceMetric, Metric},
+    sampling::TargetDistribution,
+};
+
+// only used if sampling gives up, which the `sampling_never_gives_up` test bounds below 1e-15 for
+// every built-in distribution and preset
+pub(crate) const FALLBACK_TARGET: Srgb = Srgb::new(0.8, 0.45, 0.3);
+
+/// Directions tried by [`color_at_distance`] before giving up.
+pub const MAX_DISTANCE_ATTEMPTS: usize = 256;
+
+// colors from `color_at_distance` are at most this far off the requested difference
+const DISTANCE_TOLERANCE: f32 = 0.01;
+// CIELAB is at most this wide, no color in gamut is further away along any direction
+const MAX_LAB_EXTENT: f32 = 400.0;
 
 #[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 pub struct Slider<T: Num + Into<f32>> {
     pub name: &'static str,
     pub value: T,
@@ -29,11 +54,83 @@ pub struct ColorSpaceMeta {
     pub slider_names: &'static [&'static str],
 }
 
+/// A seed that fully determines the target of a [`ColorChallenge`].
+///
+/// Targets are drawn from a ChaCha8 stream, so the same seed yields the same color on every platform.
+/// Seeds are displayed and parsed as 16 hex digits so they can be shared.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(into = "String", try_from = "String")
+)]
+pub struct ChallengeSeed(pub u64);
+
+impl ChallengeSeed {
+    pub fn random<R: Rng>(rng: &mut R) -> Self {
+        ChallengeSeed(rng.gen())
+    }
+
+    pub fn rng(&self) -> ChaCha8Rng {
+        ChaCha8Rng::seed_from_u64(self.0)
+    }
+
+    /// This seed followed by seeds drawn from it, so a whole run of rounds can be shared as one seed.
+    pub fn sequence(self) -> impl Iterator<Item = ChallengeSeed> {
+        let mut rng = self.rng();
+        std::iter::once(self).chain(std::iter::repeat_with(move || {
+            ChallengeSeed::random(&mut rng)
+        }))
+    }
+}
+
+impl Display for ChallengeSeed {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{:016x}", self.0)
+    }
+}
+
+impl From<ChallengeSeed> for String {
+    fn from(seed: ChallengeSeed) -> Self {
+        seed.to_string()
+    }
+}
+
+impl TryFrom<String> for ChallengeSeed {
+    type Error = ParseIntError;
+
+    fn try_from(s: String) -> Result<Self, Self::Error> {
+        s.parse()
+    }
+}
+
+impl FromStr for ChallengeSeed {
+    type Err = ParseIntError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let s = s.trim();
+        let s = s.strip_prefix("0x").unwrap_or(s);
+        u64::from_str_radix(s, 16).map(ChallengeSeed)
+    }
+}
+
+// not `Deserialize`, the target would bypass the exclusion check of the constructors
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 pub struct ColorChallenge {
     target: Srgb,
+    seed: ChallengeSeed,
+    metric: Metric,
+    exclusion: ExclusionPreset,
+    simulation: Option<CvdSimulation>,
+    custom: bool,
 }
 
-#[derive(Debug, Clone, Copy)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(rename_all = "snake_case")
+)]
 pub enum ExcludeReason {
     TooDark,
     TooBright,
@@ -53,45 +150,235 @@ impl Display for ExcludeReason {
 }
 
 impl ColorChallenge {
+    /// Check against the default exclusion policy.
     pub fn is_excluded(color: &Srgb) -> Option<ExcludeReason> {
-        if color.blue + color.green + color.red < 0.08 * 3. {
-            return Some(ExcludeReason::TooDark);
-        }
-        if color.blue + color.green + color.red > 0.92 * 3. {
-            return Some(ExcludeReason::TooBright);
-        }
-        let hsv: Hsv = (*color).into_color();
-        if hsv.saturation < 0.15 {
-            return Some(ExcludeReason::LowSaturation);
-        } else if hsv.saturation > 0.9 {
-            return Some(ExcludeReason::HighSaturation);
-        }
-        None
+        ExclusionPreset::Default.check(color)
+    }
+
+    /// Check against the exclusion policy this challenge was generated with.
+    pub fn check_excluded(&self, color: &Srgb) -> Option<ExcludeReason> {
+        self.exclusion.check(color)
+    }
+
+    pub fn exclusion(&self) -> ExclusionPreset {
+        self.exclusion
     }
 
     pub fn target_color(&self) -> &Srgb {
         &self.target
     }
 
+    pub fn seed(&self) -> ChallengeSeed {
+        self.seed
+    }
+
     pub fn new<R: Rng>(rng: &mut R) -> Self {
-        let target = Srgb::new(rng.gen(), rng.gen(), rng.gen());
+        Self::from_seed(ChallengeSeed::random(rng))
+    }
 
-        // avoid colors that are too dark or too bright
-        if Self::is_excluded(&target).is_some() {
-            return Self::new(rng);
+    pub fn from_seed(seed: ChallengeSeed) -> Self {
+        Self::from_seed_with(
+            seed,
+            TargetDistribution::default(),
+            ExclusionPreset::default(),
+        )
+    }
+
+    /// Like [`Self::from_seed`], the same seed, distribution and exclusion policy always yield
+    /// the same target.
+    pub fn from_seed_with(
+        seed: ChallengeSeed,
+        distribution: TargetDistribution,
+        exclusion: ExclusionPreset,
+    ) -> Self {
+        let target = distribution
+            .sample(&mut seed.rng(), &exclusion)
+            .unwrap_or(FALLBACK_TARGET);
+
+        ColorChallenge {
+            target,
+            seed,
+            metric: Metric::default(),
+            exclusion,
+            simulation: None,
+            custom: false,
         }
+    }
 
-        ColorChallenge { target }
+    /// A challenge to recall `target` instead of a sampled color, e.g. one entered by the player
+    /// or drawn from a [`PracticePalette`](crate::practice::PracticePalette).
+    ///
+    /// `seed` is recorded as is and does not determine the target.
+    pub fn from_color(target: Srgb, seed: ChallengeSeed) -> Self {
+        Self::from_color_with(target, seed, ExclusionPreset::default())
     }
 
-    pub fn compute_distance(&self, input: impl IntoColor<Lab>) -> f32 {
+    /// Like [`Self::from_color`], `target` is kept even if `exclusion` rejects it, the policy only
+    /// applies to guesses through [`Self::check_excluded`].
+    pub fn from_color_with(target: Srgb, seed: ChallengeSeed, exclusion: ExclusionPreset) -> Self {
+        ColorChallenge {
+            target,
+            seed,
+            metric: Metric::default(),
+            exclusion,
+            simulation: None,
+            custom: true,
+        }
+    }
+
+    /// Whether the target was given rather than generated from the seed.
+    pub fn is_custom(&self) -> bool {
+        self.custom
+    }
+
+    pub fn set_metric(&mut self, metric: Metric) {
+        self.metric = metric;
+    }
+
+    pub fn metric(&self) -> Metric {
+        self.metric
+    }
+
+    /// Score as seen by an observer with a color vision deficiency, both the target and the
+    /// guess are simulated before any difference is computed.
+    pub fn set_simulation(&mut self, simulation: Option<CvdSimulation>) {
+        self.simulation = simulation;
+    }
+
+    pub fn simulation(&self) -> Option<CvdSimulation> {
+        self.simulation
+    }
+
+    // target and input as scored, after the simulation if any
+    fn scored_labs(&self, input: impl IntoColor<Lab>) -> (Lab, Lab) {
         let target_lab: Lab = self.target.into_color();
         let input_lab: Lab = input.into_color();
 
-        return target_lab.improved_difference(input_lab);
+        match self.simulation {
+            Some(simulation) => {
+                let simulate = |lab: Lab| -> Lab {
+                    simulation.simulate(lab.into_color_unclamped()).into_color()
+                };
+                (simulate(target_lab), simulate(input_lab))
+            }
+            None => (target_lab, input_lab),
+        }
+    }
+
+    /// Distance from the target using the metric selected for this challenge.
+    pub fn compute_distance(&self, input: impl IntoColor<Lab>) -> f32 {
+        self.compute_distance_with(&self.metric, input)
+    }
+
+    pub fn compute_distance_with(
+        &self,
+        metric: &impl DifferenceMetric,
+        input: impl IntoColor<Lab>,
+    ) -> f32 {
+        let (target_lab, input_lab) = self.scored_labs(input);
+
+        metric.difference(target_lab, input_lab)
+    }
+
+    /// Signed lightness, chroma and hue components of the CIEDE2000 difference from the target.
+    ///
+    /// This is always plain CIEDE2000 whichever metric scores the challenge, so its `delta_e` can
+    /// differ from [`Self::compute_distance`].
+    pub fn compute_breakdown(&self, input: impl IntoColor<Lab>) -> Ciede2000Breakdown {
+        let (target_lab, input_lab) = self.scored_labs(input);
+
+        Ciede2000Breakdown::new(target_lab, input_lab)
+    }
+
+    /// A color at CIEDE2000 `delta_e` from the target that could have been a target itself,
+    /// see [`color_at_distance`].
+    pub fn distractor<R: Rng>(&self, rng: &mut R, delta_e: f32) -> Option<Srgb> {
+        color_at_distance(rng, &self.target, delta_e, &Ciede2000, &self.exclusion)
+    }
+
+    /// Distance from the target under every built-in metric, for side by side comparison.
+    pub fn compute_all_distances(&self, input: impl IntoColor<Lab>) -> Vec<(Metric, f32)> {
+        let input_lab: Lab = input.into_color();
+
+        Metric::ALL
+            .iter()
+            .map(|m| (*m, self.compute_distance_with(m, input_lab)))
+            .collect()
+    }
+}
+
+fn random_direction<R: Rng>(rng: &mut R) -> [f32; 3] {
+    loop {
+        let v: [f32; 3] = [
+            rng.gen_range(-1.0..=1.0),
+            rng.gen_range(-1.0..=1.0),
+            rng.gen_range(-1.0..=1.0),
+        ];
+        let norm = v.iter().map(|c| c * c).sum::<f32>().sqrt();
+        // rejecting outside the unit ball keeps directions uniform
+        if norm > 1e-3 && norm <= 1.0 {
+            return v.map(|c| c / norm);
+        }
     }
 }
 
+/// A color exactly `delta_e` away from `reference` under `metric`, in a random direction in
+/// CIELAB.
+///
+/// The color is inside the sRGB gamut and not excluded by `policy`. Returns `None` if no such
+/// color was found within [`MAX_DISTANCE_ATTEMPTS`] directions, e.g. when `delta_e` is too large
+/// to stay in gamut.
+pub fn color_at_distance<R: Rng>(
+    rng: &mut R,
+    reference: &Srgb,
+    delta_e: f32,
+    metric: &impl DifferenceMetric,
+    policy: &impl ExclusionPolicy,
+) -> Option<Srgb> {
+    if !(delta_e.is_finite() && delta_e > 0.0) {
+        return None;
+    }
+    let origin: Lab = (*reference).into_color_unclamped();
+
+    for _ in 0..MAX_DISTANCE_ATTEMPTS {
+        let [dl, da, db] = random_direction(rng);
+        let at = |t: f32| Lab::new(origin.l + dl * t, origin.a + da * t, origin.b + db * t);
+        let distance = |t: f32| metric.difference(origin, at(t));
+
+        let mut hi = delta_e;
+        while distance(hi) < delta_e && hi < MAX_LAB_EXTENT {
+            hi *= 2.0;
+        }
+        if distance(hi) < delta_e {
+            continue;
+        }
+
+        let mut lo = 0.0;
+        while hi - lo > 1e-4 {
+            let mid = (lo + hi) / 2.0;
+            if distance(mid) < delta_e {
+                lo = mid;
+            } else {
+                hi = mid;
+            }
+        }
+
+        let color: Srgb = at(hi).into_color_unclamped();
+        if !is_in_gamut(&color) {
+            continue;
+        }
+        let color = clip(color);
+        let color_lab: Lab = color.into_color_unclamped();
+        if policy.check(&color).is_none()
+            && (metric.difference(origin, color_lab) - delta_e).abs() <= DISTANCE_TOLERANCE
+        {
+            return Some(color);
+        }
+    }
+
+    None
+}
+
 pub fn chooser_convert<S: ColorChooser<f32>, D: ColorChooser<f32>>(
     source: &S,
     _dst: &D,
@@ -101,6 +388,16 @@ pub fn chooser_convert<S: ColorChooser<f32>, D: ColorChooser<f32>>(
     source.convert_to::<D>(sliders, dst_sliders);
 }
 
+pub fn chooser_convert_mapped<S: ColorChooser<f32>, D: ColorChooser<f32>>(
+    source: &S,
+    _dst: &D,
+    sliders: &[Slider<f32>],
+    dst_sliders: &mut [Slider<f32>],
+    mapping: GamutMapping,
+) {
+    source.convert_to_mapped::<D>(sliders, dst_sliders, mapping);
+}
+
 pub trait ColorChooser<T: Num + Copy + Into<f32>>: Default {
     fn get_meta(&self) -> ColorSpaceMeta;
 
@@ -108,6 +405,16 @@ pub trait ColorChooser<T: Num + Copy + Into<f32>>: Default {
 
     fn as_srgb(&self, sliders: &[Slider<T>]) -> Srgb;
 
+    /// Whether the slider values describe a color inside the sRGB gamut.
+    fn in_gamut(&self, sliders: &[Slider<T>]) -> bool {
+        is_in_gamut(&self.as_srgb(sliders))
+    }
+
+    /// The displayable color for the slider values, mapped into the sRGB gamut if needed.
+    fn as_srgb_mapped(&self, sliders: &[Slider<T>], mapping: GamutMapping) -> Srgb {
+        mapping.apply(self.as_srgb(sliders))
+    }
+
     fn compute_xyz(&self, sliders: &[Slider<T>]) -> Xyz;
     fn compute_lab(&self, sliders: &[Slider<T>]) -> Lab;
 
@@ -117,6 +424,18 @@ pub trait ColorChooser<T: Num + Copy + Into<f32>>: Default {
         let srgb = self.as_srgb(sliders);
         dst.clone_from_slice(&C::from_srgb(srgb));
     }
+
+    /// Like [`Self::convert_to`], but converts the gamut mapped color so every model shows the
+    /// same displayable color.
+    fn convert_to_mapped<C: ColorChooser<T>>(
+        &self,
+        sliders: &[Slider<T>],
+        dst: &mut [Slider<T>],
+        mapping: GamutMapping,
+    ) {
+        let srgb = self.as_srgb_mapped(sliders, mapping);
+        dst.clone_from_slice(&C::from_srgb(srgb));
+    }
 }
 
 #[derive(Default, Clone, Copy)]
@@ -164,6 +483,85 @@ impl ColorChooser<f32> for RGBChooser {
     }
 }
 
+/// sRGB with integer channels, the units of hex codes.
+#[derive(Default, Clone, Copy)]
+pub struct RGB8Chooser;
+
+impl RGB8Chooser {
+    /// Sliders for a `#rrggbb` or `#rgb` hex code.
+    pub fn from_hex(hex: &str) -> Option<Box<[Slider<u8>]>> {
+        parse_hex(hex).map(|srgb| Self::from_srgb(srgb.into_format()))
+    }
+
+    /// The slider values as a `#rrggbb` hex code.
+    pub fn to_hex(&self, sliders: &[Slider<u8>]) -> String {
+        format_hex(self.as_srgb(sliders))
+    }
+}
+
+impl ColorChooser<u8> for RGB8Chooser {
+    fn get_meta(&self) -> ColorSpaceMeta {
+        ColorSpaceMeta {
+            name: "RGB (0-255)",
+            info_link: "https://en.wikipedia.org/wiki/RGB_color_model",
+            slider_names: &["Red", "Green", "Blue"],
+        }
+    }
+
+    fn init_sliders(&self) -> Box<[Slider<u8>]> {
+        vec![
+            Slider::new_linear("R", 128, 0, 255),
+            Slider::new_linear("G", 128, 0, 255),
+            Slider::new_linear("B", 128, 0, 255),
+        ]
+        .into_boxed_slice()
+    }
+
+    fn as_srgb(&self, sliders: &[Slider<u8>]) -> Srgb {
+        Srgb::new(sliders[0].value, sliders[1].value, sliders[2].value).into_format()
+    }
+
+    fn compute_xyz(&self, sliders: &[Slider<u8>]) -> Xyz {
+        self.as_srgb(sliders).into_color()
+    }
+
+    fn compute_lab(&self, sliders: &[Slider<u8>]) -> Lab {
+        self.as_srgb(sliders).into_color()
+    }
+
+    fn from_srgb(srgb: Srgb) -> Box<[Slider<u8>]> {
+        let srgb: Srgb<u8> = clip(srgb).into_format();
+        vec![
+            Slider::new_linear("R", srgb.red, 0, 255),
+            Slider::new_linear("G", srgb.green, 0, 255),
+            Slider::new_linear("B", srgb.blue, 0, 255),
+        ]
+        .into_boxed_slice()
+    }
+}
+
+/// Parse a `#rrggbb` or `#rgb` hex code, the `#` is optional.
+pub fn parse_hex(hex: &str) -> Option<Srgb<u8>> {
+    let hex = hex.trim();
+    let hex = hex.strip_prefix('#').unwrap_or(hex);
+    if !hex.chars().all(|c| c.is_ascii_hexdigit()) {
+        return None;
+    }
+
+    let digits = match hex.len() {
+        3 => hex.chars().flat_map(|c| [c, c]).collect(),
+        6 => hex.to_string(),
+        _ => return None,
+    };
+    u32::from_str_radix(&digits, 16).ok().map(Srgb::from)
+}
+
+/// `color` clipped to the sRGB gamut as a `#rrggbb` hex code.
+pub fn format_hex(color: Srgb) -> String {
+    let srgb: Srgb<u8> = clip(color).into_format();
+    format!("#{:02x}{:02x}{:02x}", srgb.red, srgb.green, srgb.blue)
+}
+
 #[derive(Default, Clone, Copy)]
 pub struct HSVChooser;
 
@@ -187,7 +585,7 @@ impl ColorChooser<f32> for HSVChooser {
 
     fn as_srgb(&self, sliders: &[Slider<f32>]) -> Srgb {
         let hsv = Hsv::new(sliders[0].value, sliders[1].value, sliders[2].value);
-        hsv.into_color()
+        hsv.into_color_unclamped()
     }
 
     fn compute_xyz(&self, sliders: &[Slider<f32>]) -> Xyz {
@@ -233,8 +631,8 @@ impl ColorChooser<f32> for HSLChooser {
     }
 
     fn as_srgb(&self, sliders: &[Slider<f32>]) -> Srgb {
-        let hsv = Hsv::new(sliders[0].value, sliders[1].value, sliders[2].value);
-        hsv.into_color()
+        let hsl = Hsl::new(sliders[0].value, sliders[1].value, sliders[2].value);
+        hsl.into_color_unclamped()
     }
 
     fn compute_xyz(&self, sliders: &[Slider<f32>]) -> Xyz {
@@ -259,6 +657,66 @@ impl ColorChooser<f32> for HSLChooser {
     }
 }
 
+#[derive(Default, Clone, Copy)]
+pub struct HWBChooser;
+
+impl ColorChooser<f32> for HWBChooser {
+    fn get_meta(&self) -> ColorSpaceMeta {
+        ColorSpaceMeta {
+            name: "HWB",
+            info_link: "https://en.wikipedia.org/wiki/HWB_color_model",
+            slider_names: &["Hue", "Whiteness", "Blackness"],
+        }
+    }
+
+    fn init_sliders(&self) -> Box<[Slider<f32>]> {
+        vec![
+            Slider::new_linear("H", 180., 0.0, 360.0),
+            Slider::new_linear("W", 0.25, 0.0, 1.0),
+            Slider::new_linear("B", 0.5, 0.0, 1.0),
+        ]
+        .into_boxed_slice()
+    }
+
+    fn as_srgb(&self, sliders: &[Slider<f32>]) -> Srgb {
+        hwb_to_srgb(sliders[0].value, sliders[1].value, sliders[2].value)
+    }
+
+    fn compute_xyz(&self, sliders: &[Slider<f32>]) -> Xyz {
+        let srgb: Srgb = self.as_srgb(sliders).into_color();
+        srgb.into_color()
+    }
+
+    fn compute_lab(&self, sliders: &[Slider<f32>]) -> Lab {
+        let srgb: Srgb = self.as_srgb(sliders).into_color();
+        srgb.into_color()
+    }
+
+    fn from_srgb(srgb: Srgb) -> Box<[Slider<f32>]> {
+        let hsv: Hsv = srgb.into_color();
+        let hwb: Hwb = hsv.into_color();
+
+        vec![
+            Slider::new_linear("H", hwb.hue.into_positive_degrees(), 0.0, 360.0),
+            Slider::new_linear("W", hwb.whiteness, 0.0, 1.0),
+            Slider::new_linear("B", hwb.blackness, 0.0, 1.0),
+        ]
+        .into_boxed_slice()
+    }
+}
+
+// whiteness and blackness adding up to more than 1 are normalized to a gray, as in CSS
+pub(crate) fn hwb_to_srgb(hue: f32, whiteness: f32, blackness: f32) -> Srgb {
+    let (whiteness, blackness) = (whiteness.max(0.0), blackness.max(0.0));
+    if whiteness + blackness >= 1.0 {
+        let gray = whiteness / (whiteness + blackness);
+        return Srgb::new(gray, gray, gray);
+    }
+
+    let hsv: Hsv = Hwb::new(hue, whiteness, blackness).into_color_unclamped();
+    hsv.into_color_unclamped()
+}
+
 #[derive(Default, Clone, Copy)]
 pub struct LABChooser;
 
@@ -282,7 +740,7 @@ impl ColorChooser<f32> for LABChooser {
 
     fn as_srgb(&self, sliders: &[Slider<f32>]) -> Srgb {
         let lab = Lab::new(sliders[0].value, sliders[1].value, sliders[2].value);
-        lab.into_color()
+        lab.into_color_unclamped()
     }
 
     fn compute_xyz(&self, sliders: &[Slider<f32>]) -> Xyz {
@@ -319,17 +777,18 @@ impl ColorChooser<f32> for XYZChooser {
     }
 
     fn init_sliders(&self) -> Box<[Slider<f32>]> {
+        // wide enough for D65 white at (0.9505, 1.0, 1.089)
         vec![
-            Slider::new_linear("x", 0.5, 0.0, 1.0),
+            Slider::new_linear("x", 0.5, 0.0, 0.96),
             Slider::new_linear("y", 0.5, 0.0, 1.0),
-            Slider::new_linear("z", 0.5, 0.0, 1.0),
+            Slider::new_linear("z", 0.5, 0.0, 1.09),
         ]
         .into_boxed_slice()
     }
 
     fn as_srgb(&self, sliders: &[Slider<f32>]) -> Srgb {
         let xyz = Xyz::new(sliders[0].value, sliders[1].value, sliders[2].value);
-        xyz.into_color()
+        xyz.into_color_unclamped()
     }
 
     fn compute_xyz(&self, sliders: &[Slider<f32>]) -> Xyz {
@@ -345,9 +804,9 @@ impl ColorChooser<f32> for XYZChooser {
     fn from_srgb(srgb: Srgb) -> Box<[Slider<f32>]> {
         let xyz: Xyz = srgb.into_color();
         vec![
-            Slider::new_linear("x", xyz.x, 0.0, 1.0),
+            Slider::new_linear("x", xyz.x, 0.0, 0.96),
             Slider::new_linear("y", xyz.y, 0.0, 1.0),
-            Slider::new_linear("z", xyz.z, 0.0, 1.0),
+            Slider::new_linear("z", xyz.z, 0.0, 1.09),
         ]
         .into_boxed_slice()
     }
@@ -368,7 +827,8 @@ impl ColorChooser<f32> for LCHChooser {
     fn init_sliders(&self) -> Box<[Slider<f32>]> {
         vec![
             Slider::new_linear("L", 50., 0.0, 100.0),
-            Slider::new_linear("C", 64., 0.0, 128.0),
+            // sRGB blue reaches a chroma of about 134
+            Slider::new_linear("C", 64., 0.0, 150.0),
             Slider::new_linear("H", 180., 0.0, 360.0),
         ]
         .into_boxed_slice()
@@ -376,7 +836,7 @@ impl ColorChooser<f32> for LCHChooser {
 
     fn as_srgb(&self, sliders: &[Slider<f32>]) -> Srgb {
         let lch = Lch::new(sliders[0].value, sliders[1].value, sliders[2].value);
-        lch.into_color()
+        lch.into_color_unclamped()
     }
 
     fn compute_xyz(&self, sliders: &[Slider<f32>]) -> Xyz {
@@ -393,9 +853,245 @@ impl ColorChooser<f32> for LCHChooser {
         let lch: Lch = srgb.into_color();
         vec![
             Slider::new_linear("L", lch.l, 0.0, 100.0),
-            Slider::new_linear("C", lch.chroma, 0.0, 128.0),
+            Slider::new_linear("C", lch.chroma, 0.0, 150.0),
             Slider::new_linear("H", lch.hue.into_positive_degrees(), 0.0, 360.0),
         ]
         .into_boxed_slice()
     }
 }
+
+#[derive(Default, Clone, Copy)]
+pub struct LUVChooser;
+
+impl ColorChooser<f32> for LUVChooser {
+    fn get_meta(&self) -> ColorSpaceMeta {
+        ColorSpaceMeta {
+            name: "CIELUV",
+            info_link: "https://en.wikipedia.org/wiki/CIELUV",
+            slider_names: &["Lightness", "U", "V"],
+        }
+    }
+
+    fn init_sliders(&self) -> Box<[Slider<f32>]> {
+        // sRGB spans u from about -83 to 175 and v from about -134 to 107
+        vec![
+            Slider::new_linear("L", 50., 0.0, 100.0),
+            Slider::new_linear("u", 40., -84.0, 176.0),
+            Slider::new_linear("v", 40., -135.0, 108.0),
+        ]
+        .into_boxed_slice()
+    }
+
+    fn as_srgb(&self, sliders: &[Slider<f32>]) -> Srgb {
+        let luv: Luv = Luv::new(sliders[0].value, sliders[1].value, sliders[2].value);
+        luv.into_color_unclamped()
+    }
+
+    fn compute_xyz(&self, sliders: &[Slider<f32>]) -> Xyz {
+        let luv: Luv = Luv::new(sliders[0].value, sliders[1].value, sliders[2].value);
+        luv.into_color()
+    }
+
+    fn compute_lab(&self, sliders: &[Slider<f32>]) -> Lab {
+        self.compute_xyz(sliders).into_color()
+    }
+
+    fn from_srgb(srgb: Srgb) -> Box<[Slider<f32>]> {
+        let luv: Luv = srgb.into_color();
+        vec![
+            Slider::new_linear("L", luv.l, 0.0, 100.0),
+            Slider::new_linear("u", luv.u, -84.0, 176.0),
+            Slider::new_linear("v", luv.v, -135.0, 108.0),
+        ]
+        .into_boxed_slice()
+    }
+}
+
+#[derive(Default, Clone, Copy)]
+pub struct LCHUVChooser;
+
+impl ColorChooser<f32> for LCHUVChooser {
+    fn get_meta(&self) -> ColorSpaceMeta {
+        ColorSpaceMeta {
+            name: "CIELCh(uv)",
+            info_link: "https://en.wikipedia.org/wiki/CIELUV#Cylindrical_representation_(CIELCh)",
+            slider_names: &["Lightness", "Chroma", "Hue"],
+        }
+    }
+
+    fn init_sliders(&self) -> Box<[Slider<f32>]> {
+        vec![
+            Slider::new_linear("L", 50., 0.0, 100.0),
+            // sRGB red reaches a chroma of about 179
+            Slider::new_linear("C", 64., 0.0, 180.0),
+            Slider::new_linear("H", 180., 0.0, 360.0),
+        ]
+        .into_boxed_slice()
+    }
+
+    fn as_srgb(&self, sliders: &[Slider<f32>]) -> Srgb {
+        let lchuv: Lchuv = Lchuv::new(sliders[0].value, sliders[1].value, sliders[2].value);
+        lchuv.into_color_unclamped()
+    }
+
+    fn compute_xyz(&self, sliders: &[Slider<f32>]) -> Xyz {
+        let lchuv: Lchuv = Lchuv::new(sliders[0].value, sliders[1].value, sliders[2].value);
+        lchuv.into_color()
+    }
+
+    fn compute_lab(&self, sliders: &[Slider<f32>]) -> Lab {
+        self.compute_xyz(sliders).into_color()
+    }
+
+    fn from_srgb(srgb: Srgb) -> Box<[Slider<f32>]> {
+        let lchuv: Lchuv = srgb.into_color();
+        vec![
+            Slider::new_linear("L", lchuv.l, 0.0, 100.0),
+            Slider::new_linear("C", lchuv.chroma, 0.0, 180.0),
+            Slider::new_linear("H", lchuv.hue.into_positive_degrees(), 0.0, 360.0),
+        ]
+        .into_boxed_slice()
+    }
+}
+
+/// HSLuv, CIELCh(uv) with chroma rescaled so full saturation is the most chroma sRGB can show
+/// at each lightness and hue, every slider position is in gamut.
+#[derive(Default, Clone, Copy)]
+pub struct HSLUVChooser;
+
+impl ColorChooser<f32> for HSLUVChooser {
+    fn get_meta(&self) -> ColorSpaceMeta {
+        ColorSpaceMeta {
+            name: "HSLuv",
+            info_link: "https://www.hsluv.org/",
+            slider_names: &["Hue", "Saturation", "Lightness"],
+        }
+    }
+
+    fn init_sliders(&self) -> Box<[Slider<f32>]> {
+        vec![
+            Slider::new_linear("H", 180., 0.0, 360.0),
+            Slider::new_linear("S", 50., 0.0, 100.0),
+            Slider::new_linear("L", 50., 0.0, 100.0),
+        ]
+        .into_boxed_slice()
+    }
+
+    fn as_srgb(&self, sliders: &[Slider<f32>]) -> Srgb {
+        let hsluv: Hsluv = Hsluv::new(sliders[0].value, sliders[1].value, sliders[2].value);
+        hsluv.into_color_unclamped()
+    }
+
+    fn compute_xyz(&self, sliders: &[Slider<f32>]) -> Xyz {
+        let hsluv: Hsluv = Hsluv::new(sliders[0].value, sliders[1].value, sliders[2].value);
+        hsluv.into_color()
+    }
+
+    fn compute_lab(&self, sliders: &[Slider<f32>]) -> Lab {
+        self.compute_xyz(sliders).into_color()
+    }
+
+    fn from_srgb(srgb: Srgb) -> Box<[Slider<f32>]> {
+        let hsluv: Hsluv = srgb.into_color();
+        vec![
+            Slider::new_linear("H", hsluv.hue.into_positive_degrees(), 0.0, 360.0),
+            Slider::new_linear("S", hsluv.saturation, 0.0, 100.0),
+            Slider::new_linear("L", hsluv.l, 0.0, 100.0),
+        ]
+        .into_boxed_slice()
+    }
+}
+
+#[derive(Default, Clone, Copy)]
+pub struct OklabChooser;
+
+impl ColorChooser<f32> for OklabChooser {
+    fn get_meta(&self) -> ColorSpaceMeta {
+        ColorSpaceMeta {
+            name: "Oklab",
+            info_link: "https://bottosson.github.io/posts/oklab/",
+            slider_names: &["Lightness", "A", "B"],
+        }
+    }
+
+    fn init_sliders(&self) -> Box<[Slider<f32>]> {
+        vec![
+            Slider::new_linear("L", 0.5, 0.0, 1.0),
+            Slider::new_linear("a", 0.1, -0.4, 0.4),
+            Slider::new_linear("b", 0.1, -0.4, 0.4),
+        ]
+        .into_boxed_slice()
+    }
+
+    fn as_srgb(&self, sliders: &[Slider<f32>]) -> Srgb {
+        let oklab = Oklab::new(sliders[0].value, sliders[1].value, sliders[2].value);
+        oklab.into_color_unclamped()
+    }
+
+    fn compute_xyz(&self, sliders: &[Slider<f32>]) -> Xyz {
+        let oklab = Oklab::new(sliders[0].value, sliders[1].value, sliders[2].value);
+        oklab.into_color()
+    }
+
+    fn compute_lab(&self, sliders: &[Slider<f32>]) -> Lab {
+        let oklab = Oklab::new(sliders[0].value, sliders[1].value, sliders[2].value);
+        oklab.into_color()
+    }
+
+    fn from_srgb(srgb: Srgb) -> Box<[Slider<f32>]> {
+        let oklab: Oklab = srgb.into_color();
+        vec![
+            Slider::new_linear("L", oklab.l, 0.0, 1.0),
+            Slider::new_linear("a", oklab.a, -0.4, 0.4),
+            Slider::new_linear("b", oklab.b, -0.4, 0.4),
+        ]
+        .into_boxed_slice()
+    }
+}
+
+#[derive(Default, Clone, Copy)]
+pub struct OklchChooser;
+
+impl ColorChooser<f32> for OklchChooser {
+    fn get_meta(&self) -> ColorSpaceMeta {
+        ColorSpaceMeta {
+            name: "Oklch",
+            info_link: "https://bottosson.github.io/posts/oklab/",
+            slider_names: &["Lightness", "Chroma", "Hue"],
+        }
+    }
+
+    fn init_sliders(&self) -> Box<[Slider<f32>]> {
+        vec![
+            Slider::new_linear("L", 0.5, 0.0, 1.0),
+            Slider::new_linear("C", 0.1, 0.0, 0.4),
+            Slider::new_linear("H", 180., 0.0, 360.0),
+        ]
+        .into_boxed_slice()
+    }
+
+    fn as_srgb(&self, sliders: &[Slider<f32>]) -> Srgb {
+        let oklch = Oklch::new(sliders[0].value, sliders[1].value, sliders[2].value);
+        oklch.into_color_unclamped()
+    }
+
+    fn compute_xyz(&self, sliders: &[Slider<f32>]) -> Xyz {
+        let oklch = Oklch::new(sliders[0].value, sliders[1].value, sliders[2].value);
+        oklch.into_color()
+    }
+
+    fn compute_lab(&self, sliders: &[Slider<f32>]) -> Lab {
+        let oklch = Oklch::new(sliders[0].value, sliders[1].value, sliders[2].value);
+        oklch.into_color()
+    }
+
+    fn from_srgb(srgb: Srgb) -> Box<[Slider<f32>]> {
+        let oklch: Oklch = srgb.into_color();
+        vec![
+            Slider::new_linear("L", oklch.l, 0.0, 1.0),
+            Slider::new_linear("C", oklch.chroma, 0.0, 0.4),
+            Slider::new_linear("H", oklch.hue.into_positive_degrees(), 0.0, 360.0),
+        ]
+        .into_boxed_slice()
+    }
+}
@@ -0,0 +1,108 @@
+use palette::{
+    color_difference::EuclideanDistance, convert::IntoColorUnclamped, IntoColor, Oklab, Oklch, Srgb,
+};
+
+const GAMUT_EPSILON: f32 = 1e-4;
+
+// constants from the CSS Color 4 gamut mapping algorithm
+const JND: f32 = 0.02;
+const CHROMA_EPSILON: f32 = 0.0001;
+
+/// How a color outside the sRGB gamut is brought back into it.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum GamutMapping {
+    /// Clamp every channel independently, may shift hue and lightness.
+    Clip,
+    /// Reduce Oklch chroma until clipping is imperceptible, see
+    /// <https://www.w3.org/TR/css-color-4/#gamut-mapping>.
+    #[default]
+    ReduceChroma,
+}
+
+impl GamutMapping {
+    pub fn key(&self) -> &'static str {
+        match self {
+            GamutMapping::Clip => "clip",
+            GamutMapping::ReduceChroma => "reduce_chroma",
+        }
+    }
+
+    pub fn from_key(key: &str) -> Option<Self> {
+        [GamutMapping::Clip, GamutMapping::ReduceChroma]
+            .into_iter()
+            .find(|m| m.key() == key)
+    }
+
+    pub fn apply(&self, color: Srgb) -> Srgb {
+        match self {
+            GamutMapping::Clip => clip(color),
+            GamutMapping::ReduceChroma => reduce_chroma(color),
+        }
+    }
+}
+
+pub fn is_in_gamut(color: &Srgb) -> bool {
+    [color.red, color.green, color.blue]
+        .iter()
+        .all(|c| (-GAMUT_EPSILON..=1.0 + GAMUT_EPSILON).contains(c))
+}
+
+pub fn clip(color: Srgb) -> Srgb {
+    let clamp = |c: f32| if c.is_nan() { 0.0 } else { c.clamp(0.0, 1.0) };
+    Srgb::new(clamp(color.red), clamp(color.green), clamp(color.blue))
+}
+
+fn delta_e_ok(a: Oklch, b: Srgb) -> f32 {
+    let a: Oklab = a.into_color();
+    let b: Oklab = b.into_color();
+    a.distance(b)
+}
+
+/// CSS Color 4 gamut mapping into sRGB by binary search on Oklch chroma.
+pub fn reduce_chroma(color: Srgb) -> Srgb {
+    if is_in_gamut(&color) {
+        return clip(color);
+    }
+
+    let origin: Oklch = color.into_color_unclamped();
+    if origin.l >= 1.0 {
+        return Srgb::new(1.0, 1.0, 1.0);
+    }
+    if origin.l <= 0.0 {
+        return Srgb::new(0.0, 0.0, 0.0);
+    }
+
+    let mut current = origin;
+    let mut clipped = clip(current.into_color_unclamped());
+    if delta_e_ok(current, clipped) < JND {
+        return clipped;
+    }
+
+    let mut min = 0.0;
+    let mut max = origin.chroma;
+    let mut min_in_gamut = true;
+    while max - min > CHROMA_EPSILON {
+        let chroma = (min + max) / 2.0;
+        current.chroma = chroma;
+
+        let candidate: Srgb = current.into_color_unclamped();
+        if min_in_gamut && is_in_gamut(&candidate) {
+            min = chroma;
+            continue;
+        }
+
+        clipped = clip(candidate);
+        let e = delta_e_ok(current, clipped);
+        if e < JND {
+            if JND - e < CHROMA_EPSILON {
+                return clipped;
+            }
+            min_in_gamut = false;
+            min = chroma;
+        } else {
+            max = chroma;
+        }
+    }
+
+    clipped
+}
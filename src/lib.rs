@@ -1 +1,14 @@
+pub mod adaptive;
+pub mod css;
+pub mod cvd;
+pub mod daily;
+pub mod exclusion;
 pub mod game;
+pub mod gamut;
+pub mod metric;
+pub mod phase;
+pub mod practice;
+pub mod recognition;
+pub mod sampling;
+pub mod sequence;
+pub mod session;
@@ -0,0 +1,436 @@
+use palette::{
+    color_difference::{Ciede2000 as _, EuclideanDistance, ImprovedCiede2000 as _},
+    IntoColor, Lab, Oklab, Xyz,
+};
+
+/// A formula estimating the perceived difference between two colors.
+///
+/// All metrics take CIELAB (D65) input, lower is better and 0 is a perfect match.
+pub trait DifferenceMetric {
+    fn name(&self) -> &'static str;
+
+    fn difference(&self, reference: Lab, sample: Lab) -> f32;
+}
+
+fn chroma(lab: &Lab) -> f32 {
+    (lab.a * lab.a + lab.b * lab.b).sqrt()
+}
+
+// ΔH* from the a*b* and chroma differences, clamped against rounding below zero
+fn delta_h_squared(reference: &Lab, sample: &Lab, delta_c: f32) -> f32 {
+    let da = reference.a - sample.a;
+    let db = reference.b - sample.b;
+    (da * da + db * db - delta_c * delta_c).max(0.0)
+}
+
+/// CIE 1976 ΔE*ab, the Euclidean distance in CIELAB.
+#[derive(Debug, Default, Clone, Copy)]
+pub struct Cie76;
+
+impl DifferenceMetric for Cie76 {
+    fn name(&self) -> &'static str {
+        "CIE76"
+    }
+
+    fn difference(&self, reference: Lab, sample: Lab) -> f32 {
+        reference.distance(sample)
+    }
+}
+
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum Cie94Application {
+    #[default]
+    GraphicArts,
+    Textiles,
+}
+
+/// CIE 1994 ΔE*94, weighted by the chroma of the reference color.
+#[derive(Debug, Default, Clone, Copy)]
+pub struct Cie94 {
+    pub application: Cie94Application,
+}
+
+impl DifferenceMetric for Cie94 {
+    fn name(&self) -> &'static str {
+        match self.application {
+            Cie94Application::GraphicArts => "CIE94 (graphic arts)",
+            Cie94Application::Textiles => "CIE94 (textiles)",
+        }
+    }
+
+    fn difference(&self, reference: Lab, sample: Lab) -> f32 {
+        let (k_l, k1, k2) = match self.application {
+            Cie94Application::GraphicArts => (1.0, 0.045, 0.015),
+            Cie94Application::Textiles => (2.0, 0.048, 0.014),
+        };
+
+        let c1 = chroma(&reference);
+        let delta_l = reference.l - sample.l;
+        let delta_c = c1 - chroma(&sample);
+        let delta_h2 = delta_h_squared(&reference, &sample, delta_c);
+
+        let s_c = 1.0 + k1 * c1;
+        let s_h = 1.0 + k2 * c1;
+
+        ((delta_l / k_l).powi(2) + (delta_c / s_c).powi(2) + delta_h2 / (s_h * s_h)).sqrt()
+    }
+}
+
+/// CMC l:c (1984), defaults to the 2:1 acceptability ratio.
+#[derive(Debug, Clone, Copy)]
+pub struct Cmc {
+    pub lightness: f32,
+    pub chroma: f32,
+}
+
+impl Default for Cmc {
+    fn default() -> Self {
+        Cmc {
+            lightness: 2.0,
+            chroma: 1.0,
+        }
+    }
+}
+
+impl DifferenceMetric for Cmc {
+    fn name(&self) -> &'static str {
+        "CMC l:c"
+    }
+
+    fn difference(&self, reference: Lab, sample: Lab) -> f32 {
+        let c1 = chroma(&reference);
+        let h1 = reference
+            .b
+            .atan2(reference.a)
+            .to_degrees()
+            .rem_euclid(360.0);
+
+        let delta_l = reference.l - sample.l;
+        let delta_c = c1 - chroma(&sample);
+        let delta_h2 = delta_h_squared(&reference, &sample, delta_c);
+
+        let c1_4 = c1.powi(4);
+        let f = (c1_4 / (c1_4 + 1900.0)).sqrt();
+        let t = if (164.0..=345.0).contains(&h1) {
+            0.56 + (0.2 * (h1 + 168.0).to_radians().cos()).abs()
+        } else {
+            0.36 + (0.4 * (h1 + 35.0).to_radians().cos()).abs()
+        };
+
+        let s_l = if reference.l < 16.0 {
+            0.511
+        } else {
+            0.040975 * reference.l / (1.0 + 0.01765 * reference.l)
+        };
+        let s_c = 0.0638 * c1 / (1.0 + 0.0131 * c1) + 0.638;
+        let s_h = s_c * (f * t + 1.0 - f);
+
+        ((delta_l / (self.lightness * s_l)).powi(2)
+            + (delta_c / (self.chroma * s_c)).powi(2)
+            + delta_h2 / (s_h * s_h))
+            .sqrt()
+    }
+}
+
+/// CIEDE2000 ΔE00.
+#[derive(Debug, Default, Clone, Copy)]
+pub struct Ciede2000;
+
+impl DifferenceMetric for Ciede2000 {
+    fn name(&self) -> &'static str {
+        "CIEDE2000"
+    }
+
+    fn difference(&self, reference: Lab, sample: Lab) -> f32 {
+        reference.difference(sample)
+    }
+}
+
+/// CIEDE2000 ΔE' with the power function correction by Huang et al. (2015).
+#[derive(Debug, Default, Clone, Copy)]
+pub struct ImprovedCiede2000;
+
+impl DifferenceMetric for ImprovedCiede2000 {
+    fn name(&self) -> &'static str {
+        "CIEDE2000 ΔE'"
+    }
+
+    fn difference(&self, reference: Lab, sample: Lab) -> f32 {
+        reference.improved_difference(sample)
+    }
+}
+
+/// Euclidean distance in Oklab, scaled by 100 to be comparable with the CIELAB based metrics.
+#[derive(Debug, Default, Clone, Copy)]
+pub struct OklabEuclidean;
+
+impl DifferenceMetric for OklabEuclidean {
+    fn name(&self) -> &'static str {
+        "Oklab ΔE"
+    }
+
+    fn difference(&self, reference: Lab, sample: Lab) -> f32 {
+        let reference: Oklab = reference.into_color();
+        let sample: Oklab = sample.into_color();
+        reference.distance(sample) * 100.0
+    }
+}
+
+/// ΔE ITP (ITU-R BT.2124) on ICtCp, assuming diffuse white at 100 cd/m².
+#[derive(Debug, Default, Clone, Copy)]
+pub struct DeltaEItp;
+
+fn pq_encode(linear: f32) -> f32 {
+    const M1: f32 = 2610.0 / 16384.0;
+    const M2: f32 = 2523.0 / 4096.0 * 128.0;
+    const C1: f32 = 3424.0 / 4096.0;
+    const C2: f32 = 2413.0 / 4096.0 * 32.0;
+    const C3: f32 = 2392.0 / 4096.0 * 32.0;
+
+    let y = linear.max(0.0).powf(M1);
+    ((C1 + C2 * y) / (1.0 + C3 * y)).powf(M2)
+}
+
+// returns (I, T, P) where T = Ct / 2 and P = Cp as defined by BT.2124
+fn lab_to_itp(lab: Lab) -> (f32, f32, f32) {
+    let xyz: Xyz = lab.into_color();
+    // Y = 1 is diffuse white at 100 cd/m², 1/100 of the 10000 cd/m² PQ peak
+    let (x, y, z) = (xyz.x * 0.01, xyz.y * 0.01, xyz.z * 0.01);
+
+    let l = pq_encode(0.3593 * x + 0.6976 * y - 0.0359 * z);
+    let m = pq_encode(-0.1921 * x + 1.1005 * y + 0.0754 * z);
+    let s = pq_encode(0.0071 * x + 0.0748 * y + 0.8433 * z);
+
+    let i = 0.5 * l + 0.5 * m;
+    let ct = (6610.0 * l - 13613.0 * m + 7003.0 * s) / 4096.0;
+    let cp = (17933.0 * l - 17390.0 * m - 543.0 * s) / 4096.0;
+
+    (i, 0.5 * ct, cp)
+}
+
+impl DifferenceMetric for DeltaEItp {
+    fn name(&self) -> &'static str {
+        "ΔE ITP"
+    }
+
+    fn difference(&self, reference: Lab, sample: Lab) -> f32 {
+        let (i1, t1, p1) = lab_to_itp(reference);
+        let (i2, t2, p2) = lab_to_itp(sample);
+
+        720.0 * ((i1 - i2).powi(2) + (t1 - t2).powi(2) + (p1 - p2).powi(2)).sqrt()
+    }
+}
+
+/// The built-in metrics, selectable by key.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(into = "&'static str", try_from = "String")
+)]
+pub enum Metric {
+    Cie76,
+    Cie94GraphicArts,
+    Cie94Textiles,
+    Cmc,
+    Ciede2000,
+    #[default]
+    ImprovedCiede2000,
+    OklabEuclidean,
+    DeltaEItp,
+}
+
+impl Metric {
+    pub const ALL: [Metric; 8] = [
+        Metric::Cie76,
+        Metric::Cie94GraphicArts,
+        Metric::Cie94Textiles,
+        Metric::Cmc,
+        Metric::Ciede2000,
+        Metric::ImprovedCiede2000,
+        Metric::OklabEuclidean,
+        Metric::DeltaEItp,
+    ];
+
+    pub fn key(&self) -> &'static str {
+        match self {
+            Metric::Cie76 => "cie76",
+            Metric::Cie94GraphicArts => "cie94_graphic_arts",
+            Metric::Cie94Textiles => "cie94_textiles",
+            Metric::Cmc => "cmc",
+            Metric::Ciede2000 => "ciede2000",
+            Metric::ImprovedCiede2000 => "ciede2000_improved",
+            Metric::OklabEuclidean => "oklab",
+            Metric::DeltaEItp => "itp",
+        }
+    }
+
+    pub fn from_key(key: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|m| m.key() == key)
+    }
+}
+
+impl From<Metric> for &'static str {
+    fn from(metric: Metric) -> Self {
+        metric.key()
+    }
+}
+
+impl TryFrom<String> for Metric {
+    type Error = String;
+
+    fn try_from(key: String) -> Result<Self, Self::Error> {
+        Self::from_key(&key).ok_or_else(|| format!("unknown metric: {}", key))
+    }
+}
+
+impl DifferenceMetric for Metric {
+    fn name(&self) -> &'static str {
+        match self {
+            Metric::Cie76 => Cie76.name(),
+            Metric::Cie94GraphicArts => Cie94::default().name(),
+            Metric::Cie94Textiles => Cie94 {
+                application: Cie94Application::Textiles,
+            }
+            .name(),
+            Metric::Cmc => Cmc::default().name(),
+            Metric::Ciede2000 => Ciede2000.name(),
+            Metric::ImprovedCiede2000 => ImprovedCiede2000.name(),
+            Metric::OklabEuclidean => OklabEuclidean.name(),
+            Metric::DeltaEItp => DeltaEItp.name(),
+        }
+    }
+
+    fn difference(&self, reference: Lab, sample: Lab) -> f32 {
+        match self {
+            Metric::Cie76 => Cie76.difference(reference, sample),
+            Metric::Cie94GraphicArts => Cie94::default().difference(reference, sample),
+            Metric::Cie94Textiles => Cie94 {
+                application: Cie94Application::Textiles,
+            }
+            .difference(reference, sample),
+            Metric::Cmc => Cmc::default().difference(reference, sample),
+            Metric::Ciede2000 => Ciede2000.difference(reference, sample),
+            Metric::ImprovedCiede2000 => ImprovedCiede2000.difference(reference, sample),
+            Metric::OklabEuclidean => OklabEuclidean.difference(reference, sample),
+            Metric::DeltaEItp => DeltaEItp.difference(reference, sample),
+        }
+    }
+}
+
+/// The components of a CIEDE2000 difference, signed as sample minus reference.
+///
+/// A positive `delta_l` means the sample is lighter than the reference, a positive `delta_c`
+/// means it is more saturated and a positive `delta_h` means its hue is rotated counter-clockwise.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Ciede2000Breakdown {
+    /// ΔL', lightness difference.
+    pub delta_l: f32,
+    /// ΔC', chroma difference.
+    pub delta_c: f32,
+    /// ΔH', hue difference.
+    pub delta_h: f32,
+    /// S_L, lightness weighting.
+    pub s_l: f32,
+    /// S_C, chroma weighting.
+    pub s_c: f32,
+    /// S_H, hue weighting.
+    pub s_h: f32,
+    /// R_T, chroma-hue interaction in the blue region.
+    pub r_t: f32,
+    /// The resulting ΔE00.
+    pub delta_e: f32,
+}
+
+impl Ciede2000Breakdown {
+    pub fn new(reference: Lab, sample: Lab) -> Self {
+        const POW25_7: f32 = 6_103_515_625.0;
+
+        let c_bar = (chroma(&reference) + chroma(&sample)) / 2.0;
+        let c_bar_7 = c_bar.powi(7);
+        let g = 0.5 * (1.0 - (c_bar_7 / (c_bar_7 + POW25_7)).sqrt());
+
+        let prime = |lab: &Lab| {
+            let a = (1.0 + g) * lab.a;
+            let c = (a * a + lab.b * lab.b).sqrt();
+            let h = if c == 0.0 {
+                0.0
+            } else {
+                lab.b.atan2(a).to_degrees().rem_euclid(360.0)
+            };
+            (c, h)
+        };
+        let (c1, h1) = prime(&reference);
+        let (c2, h2) = prime(&sample);
+
+        let delta_l = sample.l - reference.l;
+        let delta_c = c2 - c1;
+        let delta_hue = if c1 * c2 == 0.0 {
+            0.0
+        } else if (h2 - h1).abs() <= 180.0 {
+            h2 - h1
+        } else if h2 > h1 {
+            h2 - h1 - 360.0
+        } else {
+            h2 - h1 + 360.0
+        };
+        let delta_h = 2.0 * (c1 * c2).sqrt() * (delta_hue / 2.0).to_radians().sin();
+
+        let l_bar = (reference.l + sample.l) / 2.0;
+        let c_bar = (c1 + c2) / 2.0;
+        let h_bar = if c1 * c2 == 0.0 {
+            h1 + h2
+        } else if (h1 - h2).abs() <= 180.0 {
+            (h1 + h2) / 2.0
+        } else if h1 + h2 < 360.0 {
+            (h1 + h2 + 360.0) / 2.0
+        } else {
+            (h1 + h2 - 360.0) / 2.0
+        };
+
+        let t = 1.0 - 0.17 * (h_bar - 30.0).to_radians().cos()
+            + 0.24 * (2.0 * h_bar).to_radians().cos()
+            + 0.32 * (3.0 * h_bar + 6.0).to_radians().cos()
+            - 0.20 * (4.0 * h_bar - 63.0).to_radians().cos();
+        let delta_theta = 30.0 * (-((h_bar - 275.0) / 25.0).powi(2)).exp();
+        let c_bar_7 = c_bar.powi(7);
+        let r_c = 2.0 * (c_bar_7 / (c_bar_7 + POW25_7)).sqrt();
+
+        let l_50 = (l_bar - 50.0).powi(2);
+        let s_l = 1.0 + 0.015 * l_50 / (20.0 + l_50).sqrt();
+        let s_c = 1.0 + 0.045 * c_bar;
+        let s_h = 1.0 + 0.015 * c_bar * t;
+        let r_t = -(2.0 * delta_theta).to_radians().sin() * r_c;
+
+        let (wl, wc, wh) = (delta_l / s_l, delta_c / s_c, delta_h / s_h);
+        let delta_e = (wl * wl + wc * wc + wh * wh + r_t * wc * wh)
+            .max(0.0)
+            .sqrt();
+
+        Ciede2000Breakdown {
+            delta_l,
+            delta_c,
+            delta_h,
+            s_l,
+            s_c,
+            s_h,
+            r_t,
+            delta_e,
+        }
+    }
+
+    /// ΔL' / S_L, the lightness term as it contributes to ΔE00.
+    pub fn weighted_lightness(&self) -> f32 {
+        self.delta_l / self.s_l
+    }
+
+    /// ΔC' / S_C, the chroma term as it contributes to ΔE00.
+    pub fn weighted_chroma(&self) -> f32 {
+        self.delta_c / self.s_c
+    }
+
+    /// ΔH' / S_H, the hue term as it contributes to ΔE00.
+    pub fn weighted_hue(&self) -> f32 {
+        self.delta_h / self.s_h
+    }
+}
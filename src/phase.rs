@@ -0,0 +1,325 @@
+use std::{cell::Cell, fmt::Display, time::Duration};
+
+use palette::Srgb;
+use rand::Rng;
+
+use crate::{
+    cvd::CvdSimulation,
+    exclusion::ExclusionPreset,
+    game::{ChallengeSeed, ColorChallenge, ExcludeReason},
+    metric::Metric,
+    recognition::{RecognitionError, RecognitionRound},
+    session::RoundResult,
+};
+
+/// How long the target stays hidden between memorizing and recalling by default.
+pub const DEFAULT_DELAY: Duration = Duration::from_secs(8);
+
+/// A monotonic time source, measured from an arbitrary fixed origin.
+pub trait Clock {
+    fn now(&self) -> Duration;
+}
+
+impl<C: Clock + ?Sized> Clock for &C {
+    fn now(&self) -> Duration {
+        (**self).now()
+    }
+}
+
+/// Wall clock time from [`std::time::Instant`], which is not available in browsers.
+#[cfg(not(all(target_arch = "wasm32", target_os = "unknown")))]
+#[derive(Debug, Clone, Copy)]
+pub struct SystemClock {
+    origin: std::time::Instant,
+}
+
+#[cfg(not(all(target_arch = "wasm32", target_os = "unknown")))]
+impl SystemClock {
+    pub fn new() -> Self {
+        SystemClock {
+            origin: std::time::Instant::now(),
+        }
+    }
+}
+
+#[cfg(not(all(target_arch = "wasm32", target_os = "unknown")))]
+impl Default for SystemClock {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(not(all(target_arch = "wasm32", target_os = "unknown")))]
+impl Clock for SystemClock {
+    fn now(&self) -> Duration {
+        self.origin.elapsed()
+    }
+}
+
+/// A clock that only moves when told to, for tests and replays.
+#[derive(Debug, Default)]
+pub struct ManualClock {
+    now: Cell<Duration>,
+}
+
+impl ManualClock {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn advance(&self, by: Duration) {
+        self.now.set(self.now.get() + by);
+    }
+}
+
+impl Clock for ManualClock {
+    fn now(&self) -> Duration {
+        self.now.get()
+    }
+}
+
+/// The steps of a round, in order.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum GamePhase {
+    /// The target is shown.
+    Memorize,
+    /// The target is hidden and nothing can be entered yet.
+    Delay,
+    /// The target is hidden and a guess can be submitted.
+    Recall,
+    /// A guess was submitted, the target and scores are revealed.
+    Scored,
+}
+
+impl GamePhase {
+    pub const fn key(self) -> &'static str {
+        match self {
+            GamePhase::Memorize => "memorize",
+            GamePhase::Delay => "delay",
+            GamePhase::Recall => "recall",
+            GamePhase::Scored => "scored",
+        }
+    }
+}
+
+impl Display for GamePhase {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.key())
+    }
+}
+
+/// An action was attempted in a phase that does not allow it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PhaseError {
+    pub action: &'static str,
+    pub phase: GamePhase,
+}
+
+impl Display for PhaseError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "cannot {} during the {} phase", self.action, self.phase)
+    }
+}
+
+impl std::error::Error for PhaseError {}
+
+/// A [`ColorChallenge`] played through memorize → delay → recall → scored.
+///
+/// The round owns the timing: the delay ends by itself once enough time has passed on the clock,
+/// and the target is only handed out while memorizing and after scoring.
+pub struct GameRound<C: Clock> {
+    challenge: ColorChallenge,
+    clock: C,
+    delay: Duration,
+    memorize_started: Duration,
+    delay_started: Option<Duration>,
+    submitted: Option<(Duration, Srgb)>,
+    recognition: Option<RecognitionRound>,
+}
+
+impl<C: Clock> GameRound<C> {
+    /// Start memorizing `challenge` now, with the [`DEFAULT_DELAY`].
+    pub fn new(challenge: ColorChallenge, clock: C) -> Self {
+        Self::with_delay(challenge, clock, DEFAULT_DELAY)
+    }
+
+    pub fn with_delay(challenge: ColorChallenge, clock: C, delay: Duration) -> Self {
+        let memorize_started = clock.now();
+        GameRound {
+            challenge,
+            clock,
+            delay,
+            memorize_started,
+            delay_started: None,
+            submitted: None,
+            recognition: None,
+        }
+    }
+
+    fn check(&self, action: &'static str, allowed: &[GamePhase]) -> Result<(), PhaseError> {
+        let phase = self.phase();
+        if allowed.contains(&phase) {
+            Ok(())
+        } else {
+            Err(PhaseError { action, phase })
+        }
+    }
+
+    // saturates so an absurdly long delay never ends instead of overflowing
+    fn recall_started(&self) -> Option<Duration> {
+        self.delay_started.map(|t| t.saturating_add(self.delay))
+    }
+
+    pub fn phase(&self) -> GamePhase {
+        if self.submitted.is_some() {
+            GamePhase::Scored
+        } else if self.recall_started().is_some_and(|t| self.clock.now() >= t) {
+            GamePhase::Recall
+        } else if self.delay_started.is_some() {
+            GamePhase::Delay
+        } else {
+            GamePhase::Memorize
+        }
+    }
+
+    pub fn delay(&self) -> Duration {
+        self.delay
+    }
+
+    /// Change the delay, only possible before it has started.
+    pub fn set_delay(&mut self, delay: Duration) -> Result<(), PhaseError> {
+        self.check("change the delay", &[GamePhase::Memorize])?;
+        self.delay = delay;
+        Ok(())
+    }
+
+    /// Time left until recall can begin, zero once it has.
+    pub fn remaining_delay(&self) -> Duration {
+        match self.recall_started() {
+            Some(t) => t.saturating_sub(self.clock.now()),
+            None => self.delay,
+        }
+    }
+
+    /// End memorization and start the delay, returning the time spent memorizing.
+    pub fn finish_memorizing(&mut self) -> Result<Duration, PhaseError> {
+        self.check("finish memorizing", &[GamePhase::Memorize])?;
+        let now = self.clock.now();
+        self.delay_started = Some(now);
+        Ok(now.saturating_sub(self.memorize_started))
+    }
+
+    /// Submit the recalled color, returning its score under the challenge metric.
+    pub fn submit(&mut self, guess: Srgb) -> Result<f32, PhaseError> {
+        self.check("submit a guess", &[GamePhase::Recall])?;
+        self.submitted = Some((self.clock.now(), guess));
+        Ok(self.challenge.compute_distance(guess))
+    }
+
+    pub fn memorize_time(&self) -> Option<Duration> {
+        self.delay_started
+            .map(|t| t.saturating_sub(self.memorize_started))
+    }
+
+    pub fn recall_time(&self) -> Option<Duration> {
+        let (submitted, _) = self.submitted?;
+        Some(submitted.saturating_sub(self.recall_started()?))
+    }
+
+    pub fn guess(&self) -> Option<Srgb> {
+        self.submitted.map(|(_, guess)| guess)
+    }
+
+    /// The target, hidden during the delay and recall phases.
+    pub fn target(&self) -> Result<&Srgb, PhaseError> {
+        self.check(
+            "reveal the target",
+            &[GamePhase::Memorize, GamePhase::Scored],
+        )?;
+        Ok(self.challenge.target_color())
+    }
+
+    /// The underlying challenge, which gives away the target and is therefore only available once scored.
+    pub fn challenge(&self) -> Result<&ColorChallenge, PhaseError> {
+        self.check("inspect the challenge", &[GamePhase::Scored])?;
+        Ok(&self.challenge)
+    }
+
+    /// The result of the round once scored, with `model` recorded as the chooser used.
+    pub fn result(&self, model: impl Into<String>) -> Option<RoundResult> {
+        Some(RoundResult::new(
+            &self.challenge,
+            self.guess()?,
+            model,
+            self.memorize_time()?,
+            self.recall_time()?,
+        ))
+    }
+
+    /// Candidate swatches for a forced choice while recalling, see [`RecognitionRound::generate`].
+    ///
+    /// The options are drawn once per round: the target is the only swatch that would stay the
+    /// same between draws, so later calls return the first options and fail if they ask for other
+    /// `alternatives` or `delta_e`. Frontends should not look at [`RecognitionRound::answer`]
+    /// before scoring.
+    pub fn recognition_round<R: Rng>(
+        &mut self,
+        rng: &mut R,
+        alternatives: usize,
+        delta_e: f32,
+    ) -> Result<&RecognitionRound, RecognitionError> {
+        self.check("draw recognition options", &[GamePhase::Recall])?;
+        let recognition = match self.recognition.take() {
+            Some(recognition) => recognition,
+            None => RecognitionRound::generate(rng, &self.challenge, alternatives, delta_e)
+                .ok_or(RecognitionError::NoDistractors { delta_e })?,
+        };
+
+        let recognition = self.recognition.insert(recognition);
+        if recognition.options().len() != alternatives || recognition.delta_e() != delta_e {
+            return Err(RecognitionError::AlreadyDrawn {
+                alternatives: recognition.options().len(),
+                delta_e: recognition.delta_e(),
+            });
+        }
+        Ok(recognition)
+    }
+
+    /// The options drawn by [`Self::recognition_round`], if any.
+    pub fn recognition(&self) -> Option<&RecognitionRound> {
+        self.recognition.as_ref()
+    }
+
+    pub fn seed(&self) -> ChallengeSeed {
+        self.challenge.seed()
+    }
+
+    /// Whether the target was given rather than generated, see [`ColorChallenge::is_custom`].
+    pub fn is_custom(&self) -> bool {
+        self.challenge.is_custom()
+    }
+
+    pub fn exclusion(&self) -> ExclusionPreset {
+        self.challenge.exclusion()
+    }
+
+    pub fn check_excluded(&self, color: &Srgb) -> Option<ExcludeReason> {
+        self.challenge.check_excluded(color)
+    }
+
+    pub fn metric(&self) -> Metric {
+        self.challenge.metric()
+    }
+
+    pub fn set_metric(&mut self, metric: Metric) {
+        self.challenge.set_metric(metric);
+    }
+
+    pub fn simulation(&self) -> Option<CvdSimulation> {
+        self.challenge.simulation()
+    }
+
+    pub fn set_simulation(&mut self, simulation: Option<CvdSimulation>) {
+        self.challenge.set_simulation(simulation);
+    }
+}
@@ -0,0 +1,245 @@
+use std::fmt::Display;
+
+use palette::Srgb;
+use rand::Rng;
+
+use crate::{
+    css::{parse_css_color, CssColorError},
+    exclusion::{ExclusionPolicy, ExclusionPreset},
+    game::{ChallengeSeed, ColorChallenge, ExcludeReason},
+    gamut::reduce_chroma,
+};
+
+/// A named color to practice.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(from = "PaletteEntryFields")
+)]
+pub struct PaletteEntry {
+    pub name: String,
+    pub color: Srgb,
+}
+
+// the serialized form of an entry, deserialized through `PaletteEntry::new`
+#[cfg(feature = "serde")]
+#[derive(serde::Deserialize)]
+struct PaletteEntryFields {
+    name: String,
+    color: Srgb,
+}
+
+#[cfg(feature = "serde")]
+impl From<PaletteEntryFields> for PaletteEntry {
+    fn from(entry: PaletteEntryFields) -> Self {
+        Self::new(entry.name, entry.color)
+    }
+}
+
+impl PaletteEntry {
+    /// Colors outside the sRGB gamut cannot be shown, they are mapped into it as CSS does.
+    pub fn new(name: impl Into<String>, color: Srgb) -> Self {
+        PaletteEntry {
+            name: name.into(),
+            color: reduce_chroma(color),
+        }
+    }
+}
+
+/// A palette could not be loaded.
+#[derive(Debug, Clone, PartialEq)]
+pub enum PaletteError {
+    /// No colors were found.
+    Empty,
+    InvalidColor {
+        name: String,
+        error: CssColorError,
+    },
+    /// The JSON is malformed or not a palette.
+    Json(String),
+}
+
+impl Display for PaletteError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            PaletteError::Empty => write!(f, "the palette has no colors"),
+            PaletteError::InvalidColor { name, error } => write!(f, "{}: {}", name, error),
+            PaletteError::Json(err) => write!(f, "invalid palette JSON: {}", err),
+        }
+    }
+}
+
+impl std::error::Error for PaletteError {}
+
+/// Colors of the player's choosing to draw targets from instead of sampling them, e.g. a brand
+/// palette.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(try_from = "PaletteEntries")
+)]
+pub struct PracticePalette {
+    entries: Vec<PaletteEntry>,
+}
+
+// the serialized form of a palette, deserialized through `PracticePalette::new`
+#[cfg(feature = "serde")]
+#[derive(serde::Deserialize)]
+struct PaletteEntries {
+    entries: Vec<PaletteEntry>,
+}
+
+#[cfg(feature = "serde")]
+impl TryFrom<PaletteEntries> for PracticePalette {
+    type Error = PaletteError;
+
+    fn try_from(palette: PaletteEntries) -> Result<Self, Self::Error> {
+        Self::new(palette.entries)
+    }
+}
+
+impl PracticePalette {
+    pub fn new(entries: Vec<PaletteEntry>) -> Result<Self, PaletteError> {
+        if entries.is_empty() {
+            return Err(PaletteError::Empty);
+        }
+        Ok(PracticePalette { entries })
+    }
+
+    /// Collect the custom properties of a stylesheet whose values are colors, e.g.
+    /// `--brand-red: #c8102e;`.
+    ///
+    /// Properties that are not colors at all, such as `--gap: 4px` or `var()` references, are
+    /// skipped, but a malformed color is an error.
+    pub fn from_css_variables(css: &str) -> Result<Self, PaletteError> {
+        let mut entries = Vec::new();
+        for declaration in strip_comments(css).split([';', '{', '}']) {
+            let Some((name, value)) = declaration.trim().split_once(':') else {
+                continue;
+            };
+            let Some(name) = name.trim().strip_prefix("--") else {
+                continue;
+            };
+            let value = value.trim().trim_end_matches("!important").trim();
+
+            match parse_css_color(value) {
+                Ok(color) => entries.push(PaletteEntry::new(name, color)),
+                Err(CssColorError::Syntax(_) | CssColorError::UnknownFunction(_)) => {}
+                Err(error) => {
+                    return Err(PaletteError::InvalidColor {
+                        name: name.to_string(),
+                        error,
+                    })
+                }
+            }
+        }
+        Self::new(entries)
+    }
+
+    /// Load a JSON object of names to CSS colors, `{"brand-red": "#c8102e"}`, or an array of CSS
+    /// colors, which are named after themselves.
+    ///
+    /// The output of [`Self::to_json`] is read back as well, `{"entries": [...]}` cannot be a
+    /// color named "entries".
+    #[cfg(feature = "serde")]
+    pub fn from_json(json: &str) -> Result<Self, PaletteError> {
+        let value: serde_json::Value =
+            serde_json::from_str(json).map_err(|err| PaletteError::Json(err.to_string()))?;
+        let pairs: Vec<(String, &serde_json::Value)> = match &value {
+            serde_json::Value::Object(map)
+                if map.len() == 1 && map.get("entries").is_some_and(|v| v.is_array()) =>
+            {
+                return serde_json::from_value(value)
+                    .map_err(|err| PaletteError::Json(err.to_string()));
+            }
+            serde_json::Value::Object(map) => map.iter().map(|(k, v)| (k.clone(), v)).collect(),
+            serde_json::Value::Array(colors) => colors
+                .iter()
+                .map(|v| (v.as_str().unwrap_or_default().to_string(), v))
+                .collect(),
+            _ => {
+                return Err(PaletteError::Json(
+                    "expected an object or an array".to_string(),
+                ))
+            }
+        };
+
+        let mut entries = Vec::new();
+        for (name, value) in pairs {
+            let css = value
+                .as_str()
+                .ok_or_else(|| PaletteError::Json(format!("{} is not a string", value)))?;
+            let color = parse_css_color(css).map_err(|error| PaletteError::InvalidColor {
+                name: name.clone(),
+                error,
+            })?;
+            entries.push(PaletteEntry::new(name, color));
+        }
+        Self::new(entries)
+    }
+
+    /// The palette with its exact colors, in order, for [`Self::from_json`].
+    #[cfg(feature = "serde")]
+    pub fn to_json(&self) -> serde_json::Result<String> {
+        serde_json::to_string(self)
+    }
+
+    pub fn entries(&self) -> &[PaletteEntry] {
+        &self.entries
+    }
+
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    /// Always false, palettes have at least one color.
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// The entries `exclusion` would never have drawn as a random target, with the reason.
+    ///
+    /// They can still be practiced, but are likely to score worse and depend on the display as
+    /// described for [`ColorChallenge::is_excluded`].
+    pub fn excluded(&self, exclusion: &ExclusionPreset) -> Vec<(&PaletteEntry, ExcludeReason)> {
+        self.entries
+            .iter()
+            .filter_map(|entry| Some((entry, exclusion.check(&entry.color)?)))
+            .collect()
+    }
+
+    /// The entry `seed` draws, the same seed always draws the same entry of the same palette.
+    pub fn draw(&self, seed: ChallengeSeed) -> &PaletteEntry {
+        &self.entries[seed.rng().gen_range(0..self.entries.len())]
+    }
+
+    /// A challenge for the entry `seed` draws.
+    pub fn challenge(&self, seed: ChallengeSeed) -> ColorChallenge {
+        self.challenge_with(seed, ExclusionPreset::default())
+    }
+
+    /// Like [`Self::challenge`], with `exclusion` applied to guesses.
+    pub fn challenge_with(
+        &self,
+        seed: ChallengeSeed,
+        exclusion: ExclusionPreset,
+    ) -> ColorChallenge {
+        ColorChallenge::from_color_with(self.draw(seed).color, seed, exclusion)
+    }
+}
+
+fn strip_comments(css: &str) -> String {
+    let mut out = String::with_capacity(css.len());
+    let mut rest = css;
+    while let Some(start) = rest.find("/*") {
+        out.push_str(&rest[..start]);
+        rest = match rest[start + 2..].find("*/") {
+            Some(end) => &rest[start + 2 + end + 2..],
+            None => "",
+        };
+    }
+    out.push_str(rest);
+    out
+}
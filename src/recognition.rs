@@ -0,0 +1,191 @@
+use palette::Srgb;
+use rand::{seq::SliceRandom, Rng};
+
+use std::fmt::Display;
+
+use crate::{game::ColorChallenge, phase::PhaseError};
+
+/// Candidate swatches shown after the delay, one of which is the target.
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct RecognitionRound {
+    options: Vec<Srgb>,
+    answer: usize,
+    delta_e: f32,
+}
+
+impl RecognitionRound {
+    /// Shuffle the target of `challenge` among `alternatives - 1` distractors, each at CIEDE2000
+    /// `delta_e` from the target.
+    ///
+    /// Returns `None` for fewer than 2 alternatives or if no distractors exist at that distance.
+    pub fn generate<R: Rng>(
+        rng: &mut R,
+        challenge: &ColorChallenge,
+        alternatives: usize,
+        delta_e: f32,
+    ) -> Option<Self> {
+        if alternatives < 2 {
+            return None;
+        }
+
+        let mut options = vec![*challenge.target_color()];
+        for _ in 1..alternatives {
+            options.push(challenge.distractor(rng, delta_e)?);
+        }
+        options.shuffle(rng);
+
+        let answer = options.iter().position(|c| c == challenge.target_color())?;
+
+        Some(RecognitionRound {
+            options,
+            answer,
+            delta_e,
+        })
+    }
+
+    pub fn options(&self) -> &[Srgb] {
+        &self.options
+    }
+
+    /// Index of the target in [`Self::options`].
+    pub fn answer(&self) -> usize {
+        self.answer
+    }
+
+    pub fn delta_e(&self) -> f32 {
+        self.delta_e
+    }
+
+    pub fn check(&self, choice: usize) -> RecognitionTrial {
+        RecognitionTrial {
+            delta_e: self.delta_e,
+            alternatives: self.options.len(),
+            correct: choice == self.answer,
+        }
+    }
+}
+
+/// The recognition options of a round could not be drawn.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum RecognitionError {
+    /// Options are only drawn while recalling.
+    WrongPhase(PhaseError),
+    /// Fewer than 2 alternatives were asked for, or no distractors exist at the distance.
+    NoDistractors { delta_e: f32 },
+    /// The options of the round were already drawn with other arguments, given here.
+    AlreadyDrawn { alternatives: usize, delta_e: f32 },
+}
+
+impl Display for RecognitionError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            RecognitionError::WrongPhase(err) => err.fmt(f),
+            RecognitionError::NoDistractors { delta_e } => {
+                write!(f, "no distractors found at ΔE {}", delta_e)
+            }
+            RecognitionError::AlreadyDrawn {
+                alternatives,
+                delta_e,
+            } => write!(
+                f,
+                "the options were already drawn as {} alternatives at ΔE {}",
+                alternatives, delta_e
+            ),
+        }
+    }
+}
+
+impl std::error::Error for RecognitionError {}
+
+impl From<PhaseError> for RecognitionError {
+    fn from(err: PhaseError) -> Self {
+        RecognitionError::WrongPhase(err)
+    }
+}
+
+/// The outcome of one forced choice.
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct RecognitionTrial {
+    pub delta_e: f32,
+    pub alternatives: usize,
+    pub correct: bool,
+}
+
+/// How often the target was picked at one distractor distance.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct LevelAccuracy {
+    pub delta_e: f32,
+    pub trials: usize,
+    pub correct: usize,
+}
+
+impl LevelAccuracy {
+    pub fn accuracy(&self) -> f32 {
+        self.correct as f32 / self.trials as f32
+    }
+}
+
+/// Accuracy against distractor distance over a set of trials.
+#[derive(Debug, Clone, PartialEq)]
+pub struct RecognitionStats {
+    /// One entry per distance, closest distractors first.
+    pub levels: Vec<LevelAccuracy>,
+    /// Expected accuracy from guessing alone.
+    pub chance: f32,
+}
+
+impl RecognitionStats {
+    pub fn from_trials(trials: &[RecognitionTrial]) -> Option<Self> {
+        if trials.is_empty() {
+            return None;
+        }
+
+        let mut levels: Vec<LevelAccuracy> = Vec::new();
+        for trial in trials {
+            match levels.iter_mut().find(|l| l.delta_e == trial.delta_e) {
+                Some(level) => {
+                    level.trials += 1;
+                    level.correct += trial.correct as usize;
+                }
+                None => levels.push(LevelAccuracy {
+                    delta_e: trial.delta_e,
+                    trials: 1,
+                    correct: trial.correct as usize,
+                }),
+            }
+        }
+        levels.sort_by(|a, b| a.delta_e.total_cmp(&b.delta_e));
+
+        let chance = trials
+            .iter()
+            .map(|t| 1.0 / t.alternatives as f32)
+            .sum::<f32>()
+            / trials.len() as f32;
+
+        Some(RecognitionStats { levels, chance })
+    }
+
+    /// Accuracy halfway between chance and perfect, the usual threshold criterion.
+    pub fn criterion(&self) -> f32 {
+        (self.chance + 1.0) / 2.0
+    }
+
+    /// The distractor distance at which accuracy reaches [`Self::criterion`], interpolated
+    /// linearly between the tested distances.
+    ///
+    /// `None` if no tested distance reaches it.
+    pub fn threshold(&self) -> Option<f32> {
+        let criterion = self.criterion();
+        let above = self.levels.iter().position(|l| l.accuracy() >= criterion)?;
+        let hit = self.levels[above];
+        if above == 0 {
+            return Some(hit.delta_e);
+        }
+
+        let miss = self.levels[above - 1];
+        let t = (criterion - miss.accuracy()) / (hit.accuracy() - miss.accuracy());
+        Some(miss.delta_e + t * (hit.delta_e - miss.delta_e))
+    }
+}
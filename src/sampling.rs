@@ -0,0 +1,100 @@
+use palette::{convert::IntoColorUnclamped, Lab, Oklab, Srgb};
+use rand::Rng;
+
+use crate::{
+    exclusion::ExclusionPolicy,
+    gamut::{clip, is_in_gamut},
+};
+
+/// Upper bound on candidates drawn for one target before giving up.
+pub const MAX_SAMPLE_ATTEMPTS: usize = 4096;
+
+// bounding boxes of the sRGB gamut, keeps the acceptance rate of rejection sampling reasonable
+const LAB_A_RANGE: (f32, f32) = (-86.2, 98.3);
+const LAB_B_RANGE: (f32, f32) = (-107.9, 94.5);
+const OKLAB_A_RANGE: (f32, f32) = (-0.234, 0.277);
+const OKLAB_B_RANGE: (f32, f32) = (-0.312, 0.199);
+
+/// How challenge targets are distributed over the allowed colors.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum TargetDistribution {
+    /// Uniform in the sRGB cube, over-represents saturated greens and blues.
+    UniformSrgb,
+    /// Uniform in CIELAB.
+    #[default]
+    UniformLab,
+    /// Uniform in Oklab.
+    UniformOklab,
+}
+
+impl TargetDistribution {
+    pub const ALL: [TargetDistribution; 3] = [
+        TargetDistribution::UniformSrgb,
+        TargetDistribution::UniformLab,
+        TargetDistribution::UniformOklab,
+    ];
+
+    pub fn key(&self) -> &'static str {
+        match self {
+            TargetDistribution::UniformSrgb => "srgb",
+            TargetDistribution::UniformLab => "lab",
+            TargetDistribution::UniformOklab => "oklab",
+        }
+    }
+
+    pub fn from_key(key: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|d| d.key() == key)
+    }
+
+    fn candidate<R: Rng>(&self, rng: &mut R) -> Srgb {
+        match self {
+            TargetDistribution::UniformSrgb => Srgb::new(rng.gen(), rng.gen(), rng.gen()),
+            TargetDistribution::UniformLab => Lab::new(
+                rng.gen_range(0.0..=100.0),
+                rng.gen_range(LAB_A_RANGE.0..=LAB_A_RANGE.1),
+                rng.gen_range(LAB_B_RANGE.0..=LAB_B_RANGE.1),
+            )
+            .into_color_unclamped(),
+            TargetDistribution::UniformOklab => Oklab::new(
+                rng.gen_range(0.0..=1.0),
+                rng.gen_range(OKLAB_A_RANGE.0..=OKLAB_A_RANGE.1),
+                rng.gen_range(OKLAB_B_RANGE.0..=OKLAB_B_RANGE.1),
+            )
+            .into_color_unclamped(),
+        }
+    }
+
+    /// The fraction of candidates [`Self::sample`] accepts under `policy`, estimated from `draws`
+    /// candidates.
+    ///
+    /// Sampling gives up with probability `(1 - rate)^MAX_SAMPLE_ATTEMPTS`.
+    pub fn acceptance_rate<R: Rng>(
+        &self,
+        rng: &mut R,
+        policy: &impl ExclusionPolicy,
+        draws: usize,
+    ) -> f32 {
+        let accepted = (0..draws)
+            .filter(|_| {
+                let candidate = self.candidate(rng);
+                is_in_gamut(&candidate) && policy.check(&clip(candidate)).is_none()
+            })
+            .count();
+        accepted as f32 / draws as f32
+    }
+
+    /// Draw an in-gamut color that is not excluded by `policy`.
+    ///
+    /// Returns `None` if no candidate was accepted within [`MAX_SAMPLE_ATTEMPTS`].
+    pub fn sample<R: Rng>(&self, rng: &mut R, policy: &impl ExclusionPolicy) -> Option<Srgb> {
+        (0..MAX_SAMPLE_ATTEMPTS).find_map(|_| {
+            let candidate = self.candidate(rng);
+            if !is_in_gamut(&candidate) {
+                return None;
+            }
+
+            let color = clip(candidate);
+            policy.check(&color).is_none().then_some(color)
+        })
+    }
+}
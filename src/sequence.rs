@@ -0,0 +1,471 @@
+use std::{fmt::Display, time::Duration};
+
+use palette::{IntoColor, Lab, Srgb};
+use rand::Rng;
+
+use crate::{
+    exclusion::ExclusionPreset,
+    game::{ChallengeSeed, FALLBACK_TARGET},
+    metric::{Ciede2000, DifferenceMetric, Metric},
+    phase::{Clock, GamePhase, PhaseError, DEFAULT_DELAY},
+    sampling::TargetDistribution,
+};
+
+pub const MIN_COLORS: usize = 2;
+pub const MAX_COLORS: usize = 6;
+
+/// Smallest CIEDE2000 difference between two targets of a sequence, so each one is worth
+/// remembering on its own.
+pub const MIN_SEPARATION: f32 = 10.0;
+
+// draws per target before settling for one closer than `MIN_SEPARATION`
+const SEPARATION_ATTEMPTS: usize = 64;
+
+/// How the targets of a [`SequenceChallenge`] are shown while memorizing.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(rename_all = "snake_case")
+)]
+pub enum Presentation {
+    /// All targets side by side.
+    #[default]
+    Together,
+    /// One target at a time, in order.
+    Sequential,
+}
+
+impl Presentation {
+    pub const ALL: [Presentation; 2] = [Presentation::Together, Presentation::Sequential];
+
+    pub fn key(&self) -> &'static str {
+        match self {
+            Presentation::Together => "together",
+            Presentation::Sequential => "sequential",
+        }
+    }
+
+    pub fn from_key(key: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|p| p.key() == key)
+    }
+}
+
+impl Display for Presentation {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.key())
+    }
+}
+
+/// Which guess is scored against which target.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(rename_all = "snake_case")
+)]
+pub enum Matching {
+    /// The n-th guess against the n-th target.
+    #[default]
+    Ordered,
+    /// Guesses are paired with targets so that the total difference is smallest, for when the
+    /// order does not matter.
+    BestMatch,
+}
+
+impl Matching {
+    pub const ALL: [Matching; 2] = [Matching::Ordered, Matching::BestMatch];
+
+    pub fn key(&self) -> &'static str {
+        match self {
+            Matching::Ordered => "ordered",
+            Matching::BestMatch => "best_match",
+        }
+    }
+
+    pub fn from_key(key: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|m| m.key() == key)
+    }
+}
+
+impl Display for Matching {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.key())
+    }
+}
+
+/// A challenge of [`MIN_COLORS`] to [`MAX_COLORS`] targets, memorized together or one at a time
+/// as set by its [`Presentation`] and recalled one by one, see [`SequenceRound`].
+// not `Deserialize`, the targets would bypass the count, exclusion and separation checks of the
+// constructors
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct SequenceChallenge {
+    targets: Vec<Srgb>,
+    seed: ChallengeSeed,
+    metric: Metric,
+    exclusion: ExclusionPreset,
+    presentation: Presentation,
+}
+
+impl SequenceChallenge {
+    pub fn new<R: Rng>(rng: &mut R, count: usize) -> Option<Self> {
+        Self::from_seed(ChallengeSeed::random(rng), count)
+    }
+
+    pub fn from_seed(seed: ChallengeSeed, count: usize) -> Option<Self> {
+        Self::from_seed_with(
+            seed,
+            count,
+            TargetDistribution::default(),
+            ExclusionPreset::default(),
+        )
+    }
+
+    /// `count` targets at least [`MIN_SEPARATION`] apart where possible, the same arguments
+    /// always yield the same targets.
+    ///
+    /// Returns `None` if `count` is not between [`MIN_COLORS`] and [`MAX_COLORS`].
+    pub fn from_seed_with(
+        seed: ChallengeSeed,
+        count: usize,
+        distribution: TargetDistribution,
+        exclusion: ExclusionPreset,
+    ) -> Option<Self> {
+        if !(MIN_COLORS..=MAX_COLORS).contains(&count) {
+            return None;
+        }
+
+        let mut rng = seed.rng();
+        let mut targets: Vec<Srgb> = Vec::with_capacity(count);
+        let mut labs: Vec<Lab> = Vec::with_capacity(count);
+        for _ in 0..count {
+            let mut target = FALLBACK_TARGET;
+            for _ in 0..SEPARATION_ATTEMPTS {
+                let Some(candidate) = distribution.sample(&mut rng, &exclusion) else {
+                    break;
+                };
+                target = candidate;
+                let lab: Lab = candidate.into_color();
+                if labs
+                    .iter()
+                    .all(|other| Ciede2000.difference(*other, lab) >= MIN_SEPARATION)
+                {
+                    break;
+                }
+            }
+            targets.push(target);
+            labs.push(target.into_color());
+        }
+
+        Some(SequenceChallenge {
+            targets,
+            seed,
+            metric: Metric::default(),
+            exclusion,
+            presentation: Presentation::default(),
+        })
+    }
+
+    pub fn targets(&self) -> &[Srgb] {
+        &self.targets
+    }
+
+    pub fn len(&self) -> usize {
+        self.targets.len()
+    }
+
+    /// Always false, a sequence has at least [`MIN_COLORS`] targets.
+    pub fn is_empty(&self) -> bool {
+        self.targets.is_empty()
+    }
+
+    pub fn seed(&self) -> ChallengeSeed {
+        self.seed
+    }
+
+    pub fn exclusion(&self) -> ExclusionPreset {
+        self.exclusion
+    }
+
+    pub fn metric(&self) -> Metric {
+        self.metric
+    }
+
+    pub fn set_metric(&mut self, metric: Metric) {
+        self.metric = metric;
+    }
+
+    pub fn presentation(&self) -> Presentation {
+        self.presentation
+    }
+
+    pub fn set_presentation(&mut self, presentation: Presentation) {
+        self.presentation = presentation;
+    }
+
+    /// Score one guess per target using the metric selected for this challenge.
+    ///
+    /// Returns `None` if the number of guesses differs from the number of targets.
+    pub fn score(&self, guesses: &[Srgb], matching: Matching) -> Option<SequenceScore> {
+        self.score_with(&self.metric, guesses, matching)
+    }
+
+    pub fn score_with(
+        &self,
+        metric: &impl DifferenceMetric,
+        guesses: &[Srgb],
+        matching: Matching,
+    ) -> Option<SequenceScore> {
+        score_sequence(metric, &self.targets, guesses, matching)
+    }
+}
+
+/// A [`SequenceChallenge`] played through the phases of a [`GameRound`](crate::phase::GameRound).
+///
+/// Memorizing shows the targets as set by the challenge's [`Presentation`], sequentially each
+/// [`Self::finish_memorizing`] moves on to the next target and only the last one starts the delay.
+/// Recalling takes one guess per target in order and the round is scored once all are in.
+pub struct SequenceRound<C: Clock> {
+    challenge: SequenceChallenge,
+    clock: C,
+    delay: Duration,
+    shown: usize,
+    delay_started: Option<Duration>,
+    guesses: Vec<Srgb>,
+}
+
+impl<C: Clock> SequenceRound<C> {
+    /// Start memorizing `challenge` now, with the [`DEFAULT_DELAY`].
+    pub fn new(challenge: SequenceChallenge, clock: C) -> Self {
+        Self::with_delay(challenge, clock, DEFAULT_DELAY)
+    }
+
+    pub fn with_delay(challenge: SequenceChallenge, clock: C, delay: Duration) -> Self {
+        let guesses = Vec::with_capacity(challenge.len());
+        SequenceRound {
+            challenge,
+            clock,
+            delay,
+            shown: 0,
+            delay_started: None,
+            guesses,
+        }
+    }
+
+    fn check(&self, action: &'static str, allowed: &[GamePhase]) -> Result<(), PhaseError> {
+        let phase = self.phase();
+        if allowed.contains(&phase) {
+            Ok(())
+        } else {
+            Err(PhaseError { action, phase })
+        }
+    }
+
+    fn recall_started(&self) -> Option<Duration> {
+        self.delay_started.map(|t| t.saturating_add(self.delay))
+    }
+
+    pub fn phase(&self) -> GamePhase {
+        if self.guesses.len() == self.challenge.len() {
+            GamePhase::Scored
+        } else if self.recall_started().is_some_and(|t| self.clock.now() >= t) {
+            GamePhase::Recall
+        } else if self.delay_started.is_some() {
+            GamePhase::Delay
+        } else {
+            GamePhase::Memorize
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.challenge.len()
+    }
+
+    /// Always false, see [`SequenceChallenge::is_empty`].
+    pub fn is_empty(&self) -> bool {
+        self.challenge.is_empty()
+    }
+
+    pub fn presentation(&self) -> Presentation {
+        self.challenge.presentation()
+    }
+
+    /// Time left until recall can begin, zero once it has.
+    pub fn remaining_delay(&self) -> Duration {
+        match self.recall_started() {
+            Some(t) => t.saturating_sub(self.clock.now()),
+            None => self.delay,
+        }
+    }
+
+    /// The targets to show with the index of the first one, all of them when scored.
+    pub fn shown(&self) -> Result<(usize, &[Srgb]), PhaseError> {
+        self.check(
+            "reveal the targets",
+            &[GamePhase::Memorize, GamePhase::Scored],
+        )?;
+        let targets = self.challenge.targets();
+        Ok(match (self.phase(), self.challenge.presentation()) {
+            (GamePhase::Memorize, Presentation::Sequential) => {
+                (self.shown, &targets[self.shown..=self.shown])
+            }
+            _ => (0, targets),
+        })
+    }
+
+    /// Move on to the next target when shown sequentially, otherwise start the delay.
+    pub fn finish_memorizing(&mut self) -> Result<(), PhaseError> {
+        self.check("finish memorizing", &[GamePhase::Memorize])?;
+        if self.challenge.presentation() == Presentation::Sequential
+            && self.shown + 1 < self.challenge.len()
+        {
+            self.shown += 1;
+        } else {
+            self.delay_started = Some(self.clock.now());
+        }
+        Ok(())
+    }
+
+    /// Index of the target the next guess is for.
+    pub fn recalling(&self) -> usize {
+        self.guesses.len()
+    }
+
+    /// Submit the guess for the target at [`Self::recalling`].
+    pub fn submit(&mut self, guess: Srgb) -> Result<(), PhaseError> {
+        self.check("submit a guess", &[GamePhase::Recall])?;
+        self.guesses.push(guess);
+        Ok(())
+    }
+
+    pub fn guesses(&self) -> &[Srgb] {
+        &self.guesses
+    }
+
+    /// The score once every target was recalled.
+    pub fn score(&self, matching: Matching) -> Option<SequenceScore> {
+        self.challenge.score(&self.guesses, matching)
+    }
+
+    /// The underlying challenge, which gives away the targets and is therefore only available once scored.
+    pub fn challenge(&self) -> Result<&SequenceChallenge, PhaseError> {
+        self.check("inspect the challenge", &[GamePhase::Scored])?;
+        Ok(&self.challenge)
+    }
+
+    pub fn seed(&self) -> ChallengeSeed {
+        self.challenge.seed()
+    }
+
+    pub fn exclusion(&self) -> ExclusionPreset {
+        self.challenge.exclusion()
+    }
+
+    pub fn metric(&self) -> Metric {
+        self.challenge.metric()
+    }
+}
+
+/// Score one guess per target, see [`SequenceChallenge::score`].
+pub fn score_sequence(
+    metric: &impl DifferenceMetric,
+    targets: &[Srgb],
+    guesses: &[Srgb],
+    matching: Matching,
+) -> Option<SequenceScore> {
+    if guesses.len() != targets.len() {
+        return None;
+    }
+
+    // costs[target][guess]
+    let costs: Vec<Vec<f32>> = targets
+        .iter()
+        .map(|target| {
+            let target: Lab = (*target).into_color();
+            guesses
+                .iter()
+                .map(|guess| metric.difference(target, (*guess).into_color()))
+                .collect()
+        })
+        .collect();
+
+    let assignment = match matching {
+        Matching::Ordered => (0..guesses.len()).collect(),
+        Matching::BestMatch => best_assignment(&costs),
+    };
+    let distances = assignment
+        .iter()
+        .enumerate()
+        .map(|(target, guess)| costs[target][*guess])
+        .collect();
+
+    Some(SequenceScore {
+        distances,
+        assignment,
+    })
+}
+
+/// The score of a [`SequenceChallenge`].
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct SequenceScore {
+    /// Difference of each target from the guess paired with it, in target order.
+    pub distances: Vec<f32>,
+    /// Index of the guess paired with each target.
+    pub assignment: Vec<usize>,
+}
+
+impl SequenceScore {
+    pub fn total(&self) -> f32 {
+        self.distances.iter().sum()
+    }
+
+    /// The total over the number of targets, comparable to single color scores.
+    pub fn mean(&self) -> f32 {
+        self.total() / self.distances.len() as f32
+    }
+}
+
+/// The pairing of rows to columns of a square cost matrix with the smallest total.
+///
+/// Exhaustive, which is plenty for up to [`MAX_COLORS`] rows.
+fn best_assignment(costs: &[Vec<f32>]) -> Vec<usize> {
+    fn search(
+        costs: &[Vec<f32>],
+        current: &mut Vec<usize>,
+        used: &mut [bool],
+        cost: f32,
+        best: &mut (f32, Vec<usize>),
+    ) {
+        if cost >= best.0 {
+            return;
+        }
+        let row = current.len();
+        if row == costs.len() {
+            *best = (cost, current.clone());
+            return;
+        }
+
+        for col in 0..costs.len() {
+            if used[col] {
+                continue;
+            }
+            used[col] = true;
+            current.push(col);
+            search(costs, current, used, cost + costs[row][col], best);
+            current.pop();
+            used[col] = false;
+        }
+    }
+
+    let mut best = (f32::INFINITY, (0..costs.len()).collect());
+    search(
+        costs,
+        &mut Vec::with_capacity(costs.len()),
+        &mut vec![false; costs.len()],
+        0.0,
+        &mut best,
+    );
+    best.1
+}
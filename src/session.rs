@@ -0,0 +1,219 @@
+use std::{borrow::Cow, time::Duration};
+
+use palette::{IntoColor, Lab, Srgb};
+
+use crate::{
+    cvd::CvdSimulation,
+    game::{ChallengeSeed, ColorChallenge},
+    metric::{DifferenceMetric, Metric},
+};
+
+/// The outcome of a single completed round.
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct RoundResult {
+    /// The seed the target was generated from, `None` for a custom target that a seed does not
+    /// reproduce, see [`ColorChallenge::is_custom`].
+    pub seed: Option<ChallengeSeed>,
+    pub target: Srgb,
+    pub guess: Srgb,
+    pub metric: Metric,
+    /// The color vision deficiency the round was scored as, see
+    /// [`ColorChallenge::set_simulation`].
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub simulation: Option<CvdSimulation>,
+    /// Key of the color model the guess was made with, e.g. `"hsv"`.
+    pub model: String,
+    pub memorize_time: Duration,
+    pub recall_time: Duration,
+    /// Difference between target and guess under `metric`, after `simulation` if any.
+    pub score: f32,
+}
+
+impl RoundResult {
+    pub fn new(
+        challenge: &ColorChallenge,
+        guess: Srgb,
+        model: impl Into<String>,
+        memorize_time: Duration,
+        recall_time: Duration,
+    ) -> Self {
+        RoundResult {
+            seed: (!challenge.is_custom()).then(|| challenge.seed()),
+            target: *challenge.target_color(),
+            guess,
+            metric: challenge.metric(),
+            simulation: challenge.simulation(),
+            model: model.into(),
+            memorize_time,
+            recall_time,
+            score: challenge.compute_distance(guess),
+        }
+    }
+
+    /// Rescore this round under a different metric, after the `simulation` it was scored with
+    /// like [`Self::score`].
+    pub fn score_with(&self, metric: &impl DifferenceMetric) -> f32 {
+        let (target, guess) = match self.simulation {
+            Some(simulation) => (
+                simulation.simulate(self.target),
+                simulation.simulate(self.guess),
+            ),
+            None => (self.target, self.guess),
+        };
+        let target: Lab = target.into_color();
+        let guess: Lab = guess.into_color();
+        metric.difference(target, guess)
+    }
+}
+
+/// Summary statistics of the scores of a session, lower is better.
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct SessionStats {
+    pub count: usize,
+    pub mean: f32,
+    pub median: f32,
+    pub best: f32,
+    pub worst: f32,
+    /// Sample standard deviation, 0 for a single round.
+    pub std_dev: f32,
+}
+
+impl SessionStats {
+    pub fn from_scores(scores: &[f32]) -> Option<Self> {
+        if scores.is_empty() {
+            return None;
+        }
+
+        let mut sorted = scores.to_vec();
+        sorted.sort_by(f32::total_cmp);
+
+        let count = sorted.len();
+        let mean = sorted.iter().sum::<f32>() / count as f32;
+        let median = if count.is_multiple_of(2) {
+            (sorted[count / 2 - 1] + sorted[count / 2]) / 2.0
+        } else {
+            sorted[count / 2]
+        };
+        let std_dev = if count > 1 {
+            let var = sorted.iter().map(|s| (s - mean).powi(2)).sum::<f32>() / (count - 1) as f32;
+            var.sqrt()
+        } else {
+            0.0
+        };
+
+        Some(SessionStats {
+            count,
+            mean,
+            median,
+            best: sorted[0],
+            worst: sorted[count - 1],
+            std_dev,
+        })
+    }
+}
+
+/// A sequence of completed rounds played by the same person.
+#[derive(Debug, Clone, Default)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(transparent)
+)]
+pub struct GameSession {
+    rounds: Vec<RoundResult>,
+}
+
+impl GameSession {
+    pub const fn new() -> Self {
+        GameSession { rounds: Vec::new() }
+    }
+
+    pub fn push(&mut self, round: RoundResult) {
+        self.rounds.push(round);
+    }
+
+    pub fn rounds(&self) -> &[RoundResult] {
+        &self.rounds
+    }
+
+    pub fn len(&self) -> usize {
+        self.rounds.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.rounds.is_empty()
+    }
+
+    pub fn clear(&mut self) {
+        self.rounds.clear();
+    }
+
+    /// Export all rounds as CSV with a header row, colors as sRGB components in `0..=1`.
+    ///
+    /// Fields are quoted as described in RFC 4180 where needed, the seed of a custom target is
+    /// left empty.
+    pub fn to_csv(&self) -> String {
+        let mut out = String::from(
+            "seed,model,metric,cvd,cvd_severity,target_r,target_g,target_b,guess_r,guess_g,guess_b,score,memorize_ms,recall_ms\n",
+        );
+
+        for r in &self.rounds {
+            out.push_str(&format!(
+                "{},{},{},{},{},{},{},{},{},{},{},{},{},{}\n",
+                r.seed.map_or(String::new(), |seed| seed.to_string()),
+                csv_field(&r.model),
+                r.metric.key(),
+                r.simulation.map_or("", |s| s.deficiency.key()),
+                r.simulation
+                    .map_or(String::new(), |s| s.severity.to_string()),
+                r.target.red,
+                r.target.green,
+                r.target.blue,
+                r.guess.red,
+                r.guess.green,
+                r.guess.blue,
+                r.score,
+                r.memorize_time.as_millis(),
+                r.recall_time.as_millis(),
+            ));
+        }
+
+        out
+    }
+
+    #[cfg(feature = "serde")]
+    pub fn to_json(&self) -> serde_json::Result<String> {
+        serde_json::to_string(self)
+    }
+
+    #[cfg(feature = "serde")]
+    pub fn from_json(json: &str) -> serde_json::Result<Self> {
+        serde_json::from_str(json)
+    }
+
+    /// Statistics with every round rescored under the metric of the latest round.
+    ///
+    /// Scores under different metrics are on different scales, so the recorded scores are not
+    /// averaged directly. Each round keeps the color vision deficiency it was scored with.
+    pub fn stats(&self) -> Option<SessionStats> {
+        let metric = self.rounds.last()?.metric;
+        self.stats_with(&metric)
+    }
+
+    /// Statistics with every round rescored under `metric`.
+    pub fn stats_with(&self, metric: &impl DifferenceMetric) -> Option<SessionStats> {
+        let scores: Vec<f32> = self.rounds.iter().map(|r| r.score_with(metric)).collect();
+        SessionStats::from_scores(&scores)
+    }
+}
+
+// quote a field containing a separator, quote or line break, doubling any quotes
+fn csv_field(field: &str) -> Cow<'_, str> {
+    if field.contains([',', '"', '\n', '\r']) {
+        Cow::Owned(format!("\"{}\"", field.replace('"', "\"\"")))
+    } else {
+        Cow::Borrowed(field)
+    }
+}
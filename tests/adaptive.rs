@@ -0,0 +1,168 @@
+use color_recall::{
+    adaptive::{AdaptiveDelay, Staircase, StaircaseConfig, StaircaseError},
+    game::{ChallengeSeed, ColorChallenge},
+    metric::{Ciede2000, Metric},
+    phase::{GamePhase, ManualClock},
+};
+use palette::Srgb;
+
+#[test]
+fn staircase_steps_one_up_two_down() {
+    let mut staircase = Staircase::new(StaircaseConfig {
+        start: 4.0,
+        min: 1.0,
+        max: 16.0,
+        step_factor: 2.0,
+        correct_within: 5.0,
+        correct_to_advance: 2,
+    })
+    .unwrap();
+
+    assert_eq!(staircase.record(1.0), 4.0);
+    assert_eq!(staircase.record(1.0), 8.0);
+    assert_eq!(staircase.record(9.0), 4.0);
+    assert_eq!(staircase.reversals(), &[8.0]);
+    assert_eq!(staircase.record(9.0), 2.0);
+    assert_eq!(staircase.record(9.0), 1.0);
+    assert_eq!(staircase.record(9.0), 1.0);
+
+    assert_eq!(staircase.trials().len(), 6);
+    assert!(staircase.trials()[0].correct);
+    assert!(!staircase.trials()[2].correct);
+    assert!(staircase.threshold().is_none());
+}
+
+#[test]
+fn staircase_converges_to_threshold() {
+    // a player who is always right below 10 seconds and always wrong above
+    let mut staircase = Staircase::new(StaircaseConfig::DELAY).unwrap();
+    for _ in 0..60 {
+        let score = if staircase.level() < 10.0 { 2.0 } else { 8.0 };
+        staircase.record(score);
+    }
+
+    let threshold = staircase.threshold().unwrap();
+    assert!(
+        (10.0 / 1.5..=10.0 * 1.5).contains(&threshold),
+        "threshold {}",
+        threshold
+    );
+}
+
+#[test]
+fn adaptive_delay_sets_round_delay() {
+    let clock = ManualClock::new();
+    let mut adaptive = AdaptiveDelay::default();
+    let initial = adaptive.delay();
+
+    for seed in 0..2 {
+        let mut round =
+            adaptive.start_round(ColorChallenge::from_seed(ChallengeSeed(seed)), &clock);
+        assert_eq!(round.delay(), adaptive.delay());
+
+        round.finish_memorizing().unwrap();
+        clock.advance(round.delay());
+        assert_eq!(round.phase(), GamePhase::Recall);
+        let target = *ColorChallenge::from_seed(ChallengeSeed(seed)).target_color();
+        round.submit(target).unwrap();
+
+        adaptive.record(&round.result("srgb").unwrap());
+    }
+
+    assert!(adaptive.delay() > initial);
+    assert_eq!(adaptive.staircase().trials().len(), 2);
+    assert!(adaptive.threshold().is_none());
+}
+
+#[test]
+fn adaptive_delay_judges_by_ciede2000() {
+    let clock = ManualClock::new();
+    let mut adaptive = AdaptiveDelay::default();
+
+    let mut challenge = ColorChallenge::from_seed(ChallengeSeed(3));
+    challenge.set_metric(Metric::DeltaEItp);
+    let guess = Srgb::new(0.5, 0.5, 0.5);
+    let mut round = adaptive.start_round(challenge, &clock);
+    round.finish_memorizing().unwrap();
+    clock.advance(round.delay());
+    round.submit(guess).unwrap();
+
+    let result = round.result("srgb").unwrap();
+    adaptive.record(&result);
+    let trial = adaptive.staircase().trials()[0];
+    assert_eq!(trial.score, result.score_with(&Ciede2000));
+    assert_ne!(trial.score, result.score);
+}
+
+#[test]
+fn invalid_configs_are_rejected() {
+    let delay = StaircaseConfig::DELAY;
+    assert_eq!(delay.validate(), Ok(()));
+
+    let invalid = [
+        (
+            "start",
+            StaircaseConfig {
+                start: f32::NAN,
+                ..delay
+            },
+        ),
+        ("min", StaircaseConfig { min: 0.0, ..delay }),
+        ("min", StaircaseConfig { min: -1.0, ..delay }),
+        (
+            "max",
+            StaircaseConfig {
+                min: 10.0,
+                max: 5.0,
+                ..delay
+            },
+        ),
+        (
+            "max",
+            StaircaseConfig {
+                max: f32::INFINITY,
+                ..delay
+            },
+        ),
+        (
+            "step_factor",
+            StaircaseConfig {
+                step_factor: 1.0,
+                ..delay
+            },
+        ),
+        (
+            "correct_within",
+            StaircaseConfig {
+                correct_within: f32::NAN,
+                ..delay
+            },
+        ),
+        (
+            "correct_to_advance",
+            StaircaseConfig {
+                correct_to_advance: 0,
+                ..delay
+            },
+        ),
+    ];
+    for (field, config) in invalid {
+        assert_eq!(
+            Staircase::new(config).unwrap_err(),
+            StaircaseError { field }
+        );
+        assert!(AdaptiveDelay::new(config).is_err());
+    }
+
+    // fine as a staircase, but not as seconds
+    let huge = StaircaseConfig { max: 1e30, ..delay };
+    assert!(Staircase::new(huge).is_ok());
+    assert_eq!(
+        AdaptiveDelay::new(huge).unwrap_err(),
+        StaircaseError { field: "max" }
+    );
+    assert_eq!(
+        AdaptiveDelay::new(delay).unwrap().delay(),
+        AdaptiveDelay::default().delay()
+    );
+}
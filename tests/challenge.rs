@@ -0,0 +1,52 @@
+use color_recall::{
+    css::{to_css, CssFormat},
+    game::{ChallengeSeed, ColorChallenge},
+};
+
+#[test]
+fn same_seed_same_target() {
+    let seed = ChallengeSeed(0x5eed);
+    let a = ColorChallenge::from_seed(seed);
+    let b = ColorChallenge::from_seed(seed);
+
+    assert_eq!(a.target_color(), b.target_color());
+    assert_eq!(a.seed(), seed);
+    assert!(ColorChallenge::is_excluded(a.target_color()).is_none());
+}
+
+// shared seeds must keep their targets, update these only for a deliberate breaking change
+#[test]
+fn seeds_map_to_known_targets() {
+    for (seed, hex) in [
+        (0, "#f77b5a"),
+        (1, "#877538"),
+        (0x5eed, "#5ef879"),
+        (0x0123_4567_89ab_cdef, "#cd4e7d"),
+    ] {
+        let challenge = ColorChallenge::from_seed(ChallengeSeed(seed));
+        assert_eq!(to_css(*challenge.target_color(), CssFormat::Hex), hex);
+    }
+}
+
+#[test]
+fn seed_roundtrip() {
+    let seed = ChallengeSeed(0x0123_4567_89ab_cdef);
+    assert_eq!(seed.to_string(), "0123456789abcdef");
+    assert_eq!("0123456789abcdef".parse::<ChallengeSeed>().unwrap(), seed);
+    assert_eq!(
+        "0x5eed".parse::<ChallengeSeed>().unwrap(),
+        ChallengeSeed(0x5eed)
+    );
+    assert!("not a seed".parse::<ChallengeSeed>().is_err());
+}
+
+#[test]
+fn seed_sequence_is_reproducible() {
+    let seed = ChallengeSeed(0x5eed);
+    let a: Vec<_> = seed.sequence().take(4).collect();
+    let b: Vec<_> = seed.sequence().take(4).collect();
+
+    assert_eq!(a, b);
+    assert_eq!(a[0], seed);
+    assert!(a[1..].iter().all(|s| *s != seed));
+}
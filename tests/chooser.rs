@@ -0,0 +1,274 @@
+use color_recall::game::{
+    format_hex, parse_hex, ColorChooser, HSLChooser, HSLUVChooser, HSVChooser, HWBChooser,
+    LABChooser, LCHChooser, LCHUVChooser, LUVChooser, OklabChooser, OklchChooser, RGB8Chooser,
+    RGBChooser, Slider, XYZChooser,
+};
+use palette::{convert::IntoColorUnclamped, Lab, Srgb, Xyz};
+use proptest::prelude::*;
+
+#[test]
+fn oklab_choosers_roundtrip() {
+    let color = Srgb::new(0.8, 0.35, 0.2);
+
+    let oklab = OklabChooser::from_srgb(color);
+    let oklch = OklchChooser::from_srgb(color);
+    for back in [OklabChooser.as_srgb(&oklab), OklchChooser.as_srgb(&oklch)] {
+        assert!((back.red - color.red).abs() < 1e-4);
+        assert!((back.green - color.green).abs() < 1e-4);
+        assert!((back.blue - color.blue).abs() < 1e-4);
+    }
+
+    for slider in oklab.iter().chain(oklch.iter()) {
+        assert!((slider.min..=slider.max).contains(&slider.value));
+    }
+}
+
+#[test]
+fn hsl_sliders_are_hsl() {
+    let sliders = [
+        Slider::new_linear("H", 0.0, 0.0, 360.0),
+        Slider::new_linear("S", 1.0, 0.0, 1.0),
+        Slider::new_linear("L", 0.25, 0.0, 1.0),
+    ];
+    let srgb = HSLChooser.as_srgb(&sliders);
+
+    assert!((srgb.red - 0.5).abs() < 1e-4);
+    assert!(srgb.green.abs() < 1e-4);
+    assert!(srgb.blue.abs() < 1e-4);
+}
+
+#[test]
+fn hwb_sliders_are_hwb() {
+    let hwb = |h: f32, w: f32, b: f32| {
+        HWBChooser.as_srgb(&[
+            Slider::new_linear("H", h, 0.0, 360.0),
+            Slider::new_linear("W", w, 0.0, 1.0),
+            Slider::new_linear("B", b, 0.0, 1.0),
+        ])
+    };
+
+    // pure hue, then mixed with white and black
+    let srgb = hwb(120.0, 0.0, 0.0);
+    assert!(srgb.red.abs() < 1e-4 && (srgb.green - 1.0).abs() < 1e-4 && srgb.blue.abs() < 1e-4);
+    let srgb = hwb(120.0, 0.2, 0.4);
+    assert!((srgb.red - 0.2).abs() < 1e-4);
+    assert!((srgb.green - 0.6).abs() < 1e-4);
+    assert!((srgb.blue - 0.2).abs() < 1e-4);
+
+    // whiteness and blackness beyond 1 give a gray in proportion, never an invalid color
+    let srgb = hwb(120.0, 0.9, 0.3);
+    for channel in [srgb.red, srgb.green, srgb.blue] {
+        assert!((channel - 0.75).abs() < 1e-4);
+    }
+    assert!(HWBChooser.in_gamut(&HWBChooser::from_srgb(srgb)));
+}
+
+#[test]
+fn luv_choosers_match_references() {
+    let red = Srgb::new(1.0, 0.0, 0.0);
+    let values = |sliders: Box<[Slider<f32>]>| sliders.iter().map(|s| s.value).collect::<Vec<_>>();
+
+    // from the CIELUV and HSLuv reference implementations
+    for (got, expected) in [
+        (values(LUVChooser::from_srgb(red)), [53.24, 175.01, 37.76]),
+        (values(LCHUVChooser::from_srgb(red)), [53.24, 179.04, 12.17]),
+        (values(HSLUVChooser::from_srgb(red)), [12.18, 100.0, 53.24]),
+    ] {
+        for (got, expected) in got.iter().zip(expected) {
+            assert!((got - expected).abs() < 0.05, "{} != {}", got, expected);
+        }
+    }
+}
+
+#[test]
+fn hex_codes() {
+    assert_eq!(parse_hex("#c86432"), Some(Srgb::new(200, 100, 50)));
+    assert_eq!(parse_hex(" C86432 "), Some(Srgb::new(200, 100, 50)));
+    assert_eq!(parse_hex("#f80"), Some(Srgb::new(255, 136, 0)));
+    for invalid in ["", "#", "#c8643", "#c864321", "#gg0000", "#+1+2+3", "#ééé"] {
+        assert_eq!(parse_hex(invalid), None, "{:?}", invalid);
+    }
+
+    assert_eq!(format_hex(Srgb::new(1.2, 0.5, -0.1)), "#ff8000");
+
+    let sliders = RGB8Chooser::from_hex("#c86432").unwrap();
+    assert_eq!(
+        sliders.iter().map(|s| s.value).collect::<Vec<_>>(),
+        [200, 100, 50]
+    );
+    assert_eq!(RGB8Chooser.to_hex(&sliders), "#c86432");
+    assert!(RGB8Chooser::from_hex("c8643").is_none());
+}
+
+proptest! {
+    #[test]
+    fn rgb8_roundtrips(r: u8, g: u8, b: u8) {
+        let color: Srgb = Srgb::new(r, g, b).into_format();
+        let sliders = RGB8Chooser::from_srgb(color);
+        prop_assert_eq!(
+            sliders.iter().map(|s| s.value).collect::<Vec<_>>(),
+            vec![r, g, b]
+        );
+        prop_assert_eq!(RGB8Chooser.as_srgb(&sliders), color);
+        prop_assert_eq!(parse_hex(&RGB8Chooser.to_hex(&sliders)), Some(Srgb::new(r, g, b)));
+
+        let lab: Lab = color.into_color_unclamped();
+        let computed = RGB8Chooser.compute_lab(&sliders);
+        prop_assert!((lab.l - computed.l).abs() < 1e-3);
+    }
+
+    #[test]
+    fn hsluv_is_always_in_gamut(h in 0.0f32..=360.0, s in 0.0f32..=100.0, l in 0.0f32..=100.0) {
+        let sliders = [
+            Slider::new_linear("H", h, 0.0, 360.0),
+            Slider::new_linear("S", s, 0.0, 100.0),
+            Slider::new_linear("L", l, 0.0, 100.0),
+        ];
+        let srgb = HSLUVChooser.as_srgb(&sliders);
+        for channel in [srgb.red, srgb.green, srgb.blue] {
+            prop_assert!((-1e-3..=1.0 + 1e-3).contains(&channel), "{:?}", srgb);
+        }
+    }
+
+    #[test]
+    fn rgb8_rounds_to_nearest(color in srgb()) {
+        let sliders = RGB8Chooser::from_srgb(color);
+        let back = RGB8Chooser.as_srgb(&sliders);
+        let half_step = 0.5 / 255.0 + 1e-6;
+        prop_assert!((back.red - color.red).abs() <= half_step);
+        prop_assert!((back.green - color.green).abs() <= half_step);
+        prop_assert!((back.blue - color.blue).abs() <= half_step);
+    }
+}
+
+fn srgb() -> impl Strategy<Value = Srgb> {
+    (0.0f32..=1.0, 0.0f32..=1.0, 0.0f32..=1.0).prop_map(|(r, g, b)| Srgb::new(r, g, b))
+}
+
+// palette bounds the HSLuv saturation with the matrix of the reference implementation, which is
+// slightly off from its sRGB one, so the most saturated colors are clamped up to ~1.5e-3 inwards
+const HSLUV_TOLERANCE: f32 = 2e-3;
+
+fn tolerance(chooser: &impl ColorChooser<f32>) -> f32 {
+    if chooser.get_meta().name == HSLUVChooser.get_meta().name {
+        HSLUV_TOLERANCE
+    } else {
+        1e-3
+    }
+}
+
+fn assert_srgb_close(a: Srgb, b: Srgb, tolerance: f32) -> Result<(), TestCaseError> {
+    prop_assert!(
+        (a.red - b.red).abs() < tolerance
+            && (a.green - b.green).abs() < tolerance
+            && (a.blue - b.blue).abs() < tolerance,
+        "{:?} != {:?}",
+        a,
+        b
+    );
+    Ok(())
+}
+
+macro_rules! chooser_properties {
+    ($($name:ident => $chooser:ident),* $(,)?) => {
+        $(
+            mod $name {
+                use super::*;
+
+                proptest! {
+                    #[test]
+                    fn from_srgb_roundtrips(color in srgb()) {
+                        let sliders = $chooser::from_srgb(color);
+                        prop_assert_eq!(sliders.len(), $chooser.init_sliders().len());
+                        for slider in sliders.iter() {
+                            prop_assert!(
+                                slider.value >= slider.min - 1e-3 && slider.value <= slider.max + 1e-3,
+                                "{} = {} outside {}..={}",
+                                slider.name,
+                                slider.value,
+                                slider.min,
+                                slider.max
+                            );
+                        }
+                        assert_srgb_close($chooser.as_srgb(&sliders), color, tolerance(&$chooser))?;
+                    }
+
+                    #[test]
+                    fn lab_and_xyz_agree_with_srgb(color in srgb()) {
+                        let sliders = $chooser::from_srgb(color);
+                        let srgb = $chooser.as_srgb(&sliders);
+
+                        let lab: Lab = srgb.into_color_unclamped();
+                        let computed = $chooser.compute_lab(&sliders);
+                        prop_assert!(
+                            (lab.l - computed.l).abs() < 0.05
+                                && (lab.a - computed.a).abs() < 0.05
+                                && (lab.b - computed.b).abs() < 0.05,
+                            "{:?} != {:?}",
+                            lab,
+                            computed
+                        );
+
+                        let xyz: Xyz = srgb.into_color_unclamped();
+                        let computed = $chooser.compute_xyz(&sliders);
+                        prop_assert!(
+                            (xyz.x - computed.x).abs() < 1e-3
+                                && (xyz.y - computed.y).abs() < 1e-3
+                                && (xyz.z - computed.z).abs() < 1e-3,
+                            "{:?} != {:?}",
+                            xyz,
+                            computed
+                        );
+                    }
+
+                    #[test]
+                    fn conversions_keep_the_color(color in srgb()) {
+                        let sliders = $chooser::from_srgb(color);
+                        for back in [
+                            through::<RGBChooser>(&$chooser, &sliders),
+                            through::<HSVChooser>(&$chooser, &sliders),
+                            through::<HSLChooser>(&$chooser, &sliders),
+                            through::<HWBChooser>(&$chooser, &sliders),
+                            through::<LABChooser>(&$chooser, &sliders),
+                            through::<XYZChooser>(&$chooser, &sliders),
+                            through::<LCHChooser>(&$chooser, &sliders),
+                            through::<OklabChooser>(&$chooser, &sliders),
+                            through::<OklchChooser>(&$chooser, &sliders),
+                            through::<LUVChooser>(&$chooser, &sliders),
+                            through::<LCHUVChooser>(&$chooser, &sliders),
+                        ] {
+                            assert_srgb_close(back, color, tolerance(&$chooser))?;
+                        }
+                        assert_srgb_close(
+                            through::<HSLUVChooser>(&$chooser, &sliders),
+                            color,
+                            HSLUV_TOLERANCE,
+                        )?;
+                    }
+                }
+            }
+        )*
+    };
+}
+
+// converts to `D` and back, as switching models in the game does
+fn through<D: ColorChooser<f32>>(source: &impl ColorChooser<f32>, sliders: &[Slider<f32>]) -> Srgb {
+    let mut dst = D::default().init_sliders();
+    source.convert_to::<D>(sliders, &mut dst);
+    D::default().as_srgb(&dst)
+}
+
+chooser_properties! {
+    rgb => RGBChooser,
+    hsv => HSVChooser,
+    hsl => HSLChooser,
+    hwb => HWBChooser,
+    lab => LABChooser,
+    xyz => XYZChooser,
+    lch => LCHChooser,
+    oklab => OklabChooser,
+    oklch => OklchChooser,
+    luv => LUVChooser,
+    lchuv => LCHUVChooser,
+    hsluv => HSLUVChooser,
+}
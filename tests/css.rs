@@ -0,0 +1,210 @@
+use color_recall::css::{parse_css_color, to_css, CssColorError, CssFormat};
+use palette::Srgb;
+
+fn close(a: Srgb, b: Srgb, tolerance: f32) -> bool {
+    (a.red - b.red).abs() < tolerance
+        && (a.green - b.green).abs() < tolerance
+        && (a.blue - b.blue).abs() < tolerance
+}
+
+fn parse(css: &str) -> Srgb {
+    parse_css_color(css).unwrap_or_else(|e| panic!("{}: {}", css, e))
+}
+
+#[test]
+fn every_syntax_parses() {
+    let orange = Srgb::new(200.0 / 255.0, 100.0 / 255.0, 50.0 / 255.0);
+    for css in [
+        "#c86432",
+        "#C86432ff",
+        "rgb(200 100 50)",
+        "rgb(200, 100, 50)",
+        "rgba(200, 100, 50, 0.5)",
+        "rgb(200 100 50 / 50%)",
+        "rgb(78.431% 39.216% 19.608%)",
+        "hsl(20 60% 49.02%)",
+        "hsl(20deg, 60%, 49.02%)",
+        "hsla(0.0556turn 60% 49.02% / 1)",
+        "hwb(20 19.61% 21.57%)",
+        "lab(54.22 38.2 46.25)",
+        "lch(54.22 59.99 50.44)",
+        "oklab(0.6138 0.1005 0.1007)",
+        "oklch(0.6138 0.1423 45.08)",
+        "color(srgb 0.7843 0.3922 0.1961)",
+        "color(display-p3 0.7338 0.4133 0.243)",
+        "  RGB(200 100 50)  ",
+    ] {
+        assert!(close(parse(css), orange, 2e-3), "{} {:?}", css, parse(css));
+    }
+
+    assert!(close(parse("#fc0"), parse("#ffcc00"), 1e-6));
+    assert!(close(parse("#fc08"), parse("#ffcc00"), 1e-6));
+    assert!(close(
+        parse("rgb(none 0 0)"),
+        Srgb::new(0.0, 0.0, 0.0),
+        1e-6
+    ));
+    assert!(close(
+        parse("hsl(3.1416rad 100% 50%)"),
+        parse("hsl(180 100% 50%)"),
+        1e-3
+    ));
+    assert!(close(
+        parse("hsl(200grad 100% 50%)"),
+        parse("hsl(180 100% 50%)"),
+        1e-3
+    ));
+    assert!(close(
+        parse("color(srgb-linear 0.214 0.214 0.214)"),
+        Srgb::new(0.5, 0.5, 0.5),
+        1e-3
+    ));
+    // whiteness and blackness beyond 100% are normalized to a gray
+    assert!(close(
+        parse("hwb(120 60% 60%)"),
+        Srgb::new(0.5, 0.5, 0.5),
+        1e-6
+    ));
+}
+
+#[test]
+fn known_serializations() {
+    let red = Srgb::new(1.0, 0.0, 0.0);
+    let expected = [
+        (CssFormat::Hex, "#ff0000"),
+        (CssFormat::Rgb, "rgb(255 0 0)"),
+        (CssFormat::Hsl, "hsl(0 100% 50%)"),
+        (CssFormat::Hwb, "hwb(0 0% 0%)"),
+        (CssFormat::Lab, "lab(54.29 80.8"),
+        (CssFormat::Lch, "lch(54.29 106.8"),
+        (CssFormat::Oklab, "oklab(0.628 0.2249 0.1258)"),
+        (CssFormat::Oklch, "oklch(0.628 0.2577 29.23)"),
+        (
+            CssFormat::DisplayP3,
+            "color(display-p3 0.9175 0.2003 0.1386)",
+        ),
+    ];
+    for (format, css) in expected {
+        let written = to_css(red, format);
+        assert!(written.starts_with(css), "{} is not {}", written, css);
+    }
+
+    assert_eq!(
+        to_css(Srgb::new(0.5, 0.5, 0.5), CssFormat::Hsl),
+        "hsl(0 0% 50%)"
+    );
+    assert_eq!(
+        to_css(Srgb::new(0.0, 0.0, 0.0), CssFormat::Lab),
+        "lab(0 0 0)"
+    );
+}
+
+#[test]
+fn channels_are_rounded() {
+    // 0.999 * 255 = 254.7, truncation would give 254
+    let color = Srgb::new(0.999, 0.5, 0.001);
+    assert_eq!(to_css(color, CssFormat::Rgb), "rgb(255 128 0)");
+    assert_eq!(to_css(color, CssFormat::Hex), "#ff8000");
+}
+
+#[test]
+fn formats_roundtrip() {
+    let colors = [
+        Srgb::new(0.78, 0.39, 0.2),
+        Srgb::new(0.1, 0.6, 0.9),
+        Srgb::new(0.0, 0.0, 1.0),
+        Srgb::new(0.95, 0.95, 0.3),
+        Srgb::new(0.3, 0.3, 0.3),
+    ];
+    for color in colors {
+        for format in CssFormat::ALL {
+            let css = to_css(color, format);
+            // hex and rgb are limited to 8 bits per channel
+            let tolerance = match format {
+                CssFormat::Hex | CssFormat::Rgb => 2.5e-3,
+                _ => 1e-3,
+            };
+            assert!(
+                close(parse(&css), color, tolerance),
+                "{:?} {} {:?}",
+                color,
+                css,
+                parse(&css)
+            );
+        }
+    }
+}
+
+#[test]
+fn wide_gamut_formats_keep_out_of_gamut_colors() {
+    let p3_green = parse("color(display-p3 0 1 0)");
+    assert!(p3_green.red < 0.0 && p3_green.green > 1.0);
+
+    for format in CssFormat::ALL {
+        let roundtrip = parse(&to_css(p3_green, format));
+        if format.is_wide_gamut() {
+            assert!(close(roundtrip, p3_green, 1e-3), "{}", format);
+        } else {
+            assert!(roundtrip.red >= 0.0 && roundtrip.green <= 1.0, "{}", format);
+        }
+    }
+}
+
+#[test]
+fn format_keys() {
+    for format in CssFormat::ALL {
+        assert_eq!(CssFormat::from_key(format.key()), Some(format));
+    }
+    assert_eq!(CssFormat::from_key("cmyk"), None);
+}
+
+#[test]
+fn invalid_colors() {
+    assert_eq!(
+        parse_css_color("#12345"),
+        Err(CssColorError::InvalidHex("#12345".to_string()))
+    );
+    assert_eq!(
+        parse_css_color("#ggg"),
+        Err(CssColorError::InvalidHex("#ggg".to_string()))
+    );
+    // multi-byte characters where the alpha digits would be sliced off
+    for hex in ["#12é", "#12345é1", "#ééé"] {
+        assert_eq!(
+            parse_css_color(hex),
+            Err(CssColorError::InvalidHex(hex.to_string()))
+        );
+    }
+    assert_eq!(
+        parse_css_color("cmyk(0 0 0 0)"),
+        Err(CssColorError::UnknownFunction("cmyk".to_string()))
+    );
+    assert_eq!(
+        parse_css_color("color(rec2020 1 0 0)"),
+        Err(CssColorError::UnknownColorSpace("rec2020".to_string()))
+    );
+    assert_eq!(
+        parse_css_color("rgb(1 2)"),
+        Err(CssColorError::ComponentCount {
+            function: "rgb".to_string(),
+            expected: 3,
+            got: 2
+        })
+    );
+    assert_eq!(
+        parse_css_color("hsl(20 60 blue)"),
+        Err(CssColorError::InvalidComponent("blue".to_string()))
+    );
+    assert_eq!(
+        parse_css_color("hsl(20% 60% 50%)"),
+        Err(CssColorError::InvalidComponent("20%".to_string()))
+    );
+    assert!(matches!(
+        parse_css_color("red"),
+        Err(CssColorError::Syntax(_))
+    ));
+    assert!(matches!(
+        parse_css_color("rgb(1 2 3"),
+        Err(CssColorError::Syntax(_))
+    ));
+}
@@ -0,0 +1,93 @@
+use color_recall::{
+    cvd::{CvdMode, CvdSimulation, Deficiency, Simulated},
+    game::{ChallengeSeed, ColorChallenge},
+    metric::{Ciede2000, DifferenceMetric, Metric},
+};
+use palette::{IntoColor, Lab, Srgb};
+
+fn close(a: Srgb, b: Srgb) -> bool {
+    (a.red - b.red).abs() < 2e-3
+        && (a.green - b.green).abs() < 2e-3
+        && (a.blue - b.blue).abs() < 2e-3
+}
+
+#[test]
+fn grays_and_normal_vision_are_unchanged() {
+    for deficiency in Deficiency::ALL {
+        for step in 0..=20 {
+            let simulation = CvdSimulation::new(deficiency, step as f32 / 20.0);
+            for gray in [0.0, 0.2, 0.5, 0.8, 1.0] {
+                let gray = Srgb::new(gray, gray, gray);
+                assert!(close(simulation.simulate(gray), gray), "{:?}", simulation);
+            }
+        }
+
+        let normal = CvdSimulation::new(deficiency, 0.0);
+        let color = Srgb::new(0.8, 0.3, 0.6);
+        assert!(close(normal.simulate(color), color));
+    }
+
+    assert_eq!(CvdSimulation::new(Deficiency::Protan, 3.0).severity, 1.0);
+    assert_eq!(CvdSimulation::new(Deficiency::Protan, -1.0).severity, 0.0);
+    assert_eq!(
+        CvdSimulation::new(Deficiency::Protan, f32::NAN).severity,
+        0.0
+    );
+}
+
+#[test]
+fn severities_are_interpolated() {
+    let low = CvdSimulation::new(Deficiency::Deutan, 0.5).matrix();
+    let high = CvdSimulation::new(Deficiency::Deutan, 0.6).matrix();
+    let mid = CvdSimulation::new(Deficiency::Deutan, 0.55).matrix();
+
+    for row in 0..3 {
+        for col in 0..3 {
+            let expected = (low[row][col] + high[row][col]) / 2.0;
+            assert!((mid[row][col] - expected).abs() < 1e-5);
+        }
+    }
+}
+
+#[test]
+fn red_green_confusion() {
+    let red: Lab = Srgb::new(0.75, 0.4, 0.3).into_color();
+    let green: Lab = Srgb::new(0.55, 0.5, 0.3).into_color();
+    let normal = Ciede2000.difference(red, green);
+
+    let simulated = |deficiency| {
+        Simulated {
+            simulation: CvdSimulation::dichromacy(deficiency),
+            metric: Ciede2000,
+        }
+        .difference(red, green)
+    };
+
+    assert!(simulated(Deficiency::Protan) < normal / 2.0);
+    assert!(simulated(Deficiency::Deutan) < normal / 2.0);
+    assert!(simulated(Deficiency::Tritan) > normal / 2.0);
+}
+
+#[test]
+fn challenge_scores_in_simulated_space() {
+    let mut challenge = ColorChallenge::from_seed(ChallengeSeed(5));
+    let target = *challenge.target_color();
+    let guess = Srgb::new(target.red * 0.9, target.green, target.blue * 1.05);
+    let simulation = CvdSimulation::new(Deficiency::Tritan, 0.7);
+
+    challenge.set_simulation(Some(simulation));
+    assert!(challenge.compute_distance(target) < 1e-3);
+
+    let expected = Simulated {
+        simulation,
+        metric: Metric::default(),
+    }
+    .difference(target.into_color(), guess.into_color());
+    assert!((challenge.compute_distance(guess) - expected).abs() < 1e-4);
+
+    challenge.set_simulation(None);
+    assert!((challenge.compute_distance(guess) - expected).abs() > 1e-4);
+
+    assert_eq!(CvdMode::from_key("scoring"), Some(CvdMode::Scoring));
+    assert_eq!(Deficiency::from_key("tritan"), Some(Deficiency::Tritan));
+}
@@ -0,0 +1,61 @@
+use color_recall::{
+    css::{to_css, CssFormat},
+    daily::{daily_challenges, daily_seed, UtcDate, DAILY_CHALLENGE_ROUNDS},
+};
+
+#[test]
+fn date_conversion() {
+    assert_eq!(
+        UtcDate::from_days_since_epoch(0),
+        UtcDate::new(1970, 1, 1).unwrap()
+    );
+    assert_eq!(
+        UtcDate::from_unix_seconds(1_709_164_800).to_string(),
+        "2024-02-29"
+    );
+    assert_eq!(UtcDate::from_unix_seconds(-1).to_string(), "1969-12-31");
+
+    let date = UtcDate::new(2026, 10, 17).unwrap();
+    assert_eq!(
+        UtcDate::from_days_since_epoch(date.days_since_epoch()),
+        date
+    );
+
+    assert!(UtcDate::new(2023, 2, 29).is_none());
+    assert!(UtcDate::new(2024, 13, 1).is_none());
+}
+
+#[test]
+fn daily_challenge_is_deterministic() {
+    let date = UtcDate::new(2026, 10, 17).unwrap();
+    let a = daily_challenges(date, DAILY_CHALLENGE_ROUNDS);
+    let b = daily_challenges(date, DAILY_CHALLENGE_ROUNDS);
+
+    assert_eq!(a.len(), DAILY_CHALLENGE_ROUNDS);
+    for (a, b) in a.iter().zip(b.iter()) {
+        assert_eq!(a.target_color(), b.target_color());
+    }
+
+    let next_day = UtcDate::new(2026, 10, 18).unwrap();
+    assert_ne!(daily_seed(date, 0), daily_seed(next_day, 0));
+    assert_ne!(daily_seed(date, 0), daily_seed(date, 1));
+}
+
+// everyone playing on a given day must get the same targets, as in the seeds test of challenge.rs
+#[test]
+fn daily_seeds_map_to_known_targets() {
+    let date = UtcDate::new(2026, 10, 17).unwrap();
+    let expected = [
+        ("65bf28bfcd722269", "#a89ace"),
+        ("c905f856b924d196", "#88a38c"),
+        ("567c10ce3c532527", "#dc8c99"),
+    ];
+    let challenges = daily_challenges(date, expected.len());
+    for (round, (seed, hex)) in expected.into_iter().enumerate() {
+        assert_eq!(daily_seed(date, round).to_string(), seed);
+        assert_eq!(
+            to_css(*challenges[round].target_color(), CssFormat::Hex),
+            hex
+        );
+    }
+}
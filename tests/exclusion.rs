@@ -0,0 +1,64 @@
+use color_recall::{
+    exclusion::{ExclusionPolicy, ExclusionPreset},
+    game::{ColorChallenge, ExcludeReason},
+};
+use palette::Srgb;
+
+#[test]
+fn default_matches_legacy_check() {
+    // expected reasons under the thresholds of the original check: mean channel value within
+    // 0.08..=0.92, then HSV saturation within 0.15..=0.9
+    let cases = [
+        (Srgb::new(0.02, 0.03, 0.05), Some(ExcludeReason::TooDark)),
+        (Srgb::new(0.1, 0.07, 0.06), Some(ExcludeReason::TooDark)),
+        (Srgb::new(0.95, 0.97, 0.99), Some(ExcludeReason::TooBright)),
+        (Srgb::new(0.93, 0.92, 0.92), Some(ExcludeReason::TooBright)),
+        (
+            Srgb::new(0.5, 0.5, 0.52),
+            Some(ExcludeReason::LowSaturation),
+        ),
+        (
+            Srgb::new(1.0, 0.0, 0.05),
+            Some(ExcludeReason::HighSaturation),
+        ),
+        (Srgb::new(0.7, 0.3, 0.4), None),
+        (Srgb::new(0.2, 0.5, 0.3), None),
+    ];
+    for (color, expected) in cases {
+        assert_eq!(
+            ExclusionPreset::Default.check(&color),
+            expected,
+            "{:?}",
+            color
+        );
+        assert_eq!(ColorChallenge::is_excluded(&color), expected, "{:?}", color);
+    }
+}
+
+#[test]
+fn presets() {
+    let dark_navy = Srgb::new(0.05, 0.08, 0.2);
+    let pastel = Srgb::new(0.95, 0.85, 0.88);
+    let mid = Srgb::new(0.7, 0.3, 0.4);
+
+    for color in [dark_navy, pastel, mid] {
+        assert!(ExclusionPreset::None.check(&color).is_none());
+    }
+    assert_eq!(
+        ExclusionPreset::DisplayRobust.check(&dark_navy),
+        Some(ExcludeReason::TooDark)
+    );
+    assert!(ExclusionPreset::DisplayRobust.check(&pastel).is_some());
+    assert!(ExclusionPreset::Strict.check(&pastel).is_some());
+    for preset in [
+        ExclusionPreset::Default,
+        ExclusionPreset::Strict,
+        ExclusionPreset::DisplayRobust,
+    ] {
+        assert!(preset.check(&mid).is_none(), "{:?}", preset);
+    }
+
+    for preset in ExclusionPreset::ALL {
+        assert_eq!(ExclusionPreset::from_key(preset.key()), Some(preset));
+    }
+}
@@ -0,0 +1,47 @@
+use color_recall::{
+    game::{ColorChooser, LABChooser, LCHChooser, Slider},
+    gamut::{is_in_gamut, GamutMapping},
+};
+use palette::{IntoColor, Oklch, Srgb};
+
+#[test]
+fn in_gamut_colors_are_untouched() {
+    let color = Srgb::new(0.2, 0.6, 0.4);
+    for mapping in [GamutMapping::Clip, GamutMapping::ReduceChroma] {
+        assert_eq!(mapping.apply(color), color);
+    }
+}
+
+#[test]
+fn reduce_chroma_preserves_lightness_and_hue() {
+    // a very saturated cyan far outside sRGB
+    let sliders = [
+        Slider::new_linear("L", 70.0, 0.0, 100.0),
+        Slider::new_linear("C", 120.0, 0.0, 128.0),
+        Slider::new_linear("H", 200.0, 0.0, 360.0),
+    ];
+    assert!(!LCHChooser.in_gamut(&sliders));
+
+    let raw: Oklch = LCHChooser.as_srgb(&sliders).into_color();
+    let mapped = LCHChooser.as_srgb_mapped(&sliders, GamutMapping::ReduceChroma);
+    assert!(is_in_gamut(&mapped));
+
+    let mapped_oklch: Oklch = mapped.into_color();
+    assert!((mapped_oklch.l - raw.l).abs() < 0.02);
+    let hue_diff = (mapped_oklch.hue - raw.hue).into_degrees().abs();
+    assert!(hue_diff < 3.0, "hue moved by {}", hue_diff);
+    assert!(mapped_oklch.chroma < raw.chroma);
+}
+
+#[test]
+fn clip_is_in_gamut() {
+    let sliders = [
+        Slider::new_linear("L", 30.0, 0.0, 100.0),
+        Slider::new_linear("a", -120.0, -128.0, 128.0),
+        Slider::new_linear("b", 110.0, -128.0, 128.0),
+    ];
+    assert!(!LABChooser.in_gamut(&sliders));
+    for mapping in [GamutMapping::Clip, GamutMapping::ReduceChroma] {
+        assert!(is_in_gamut(&LABChooser.as_srgb_mapped(&sliders, mapping)));
+    }
+}
@@ -0,0 +1,77 @@
+use color_recall::metric::{Ciede2000Breakdown, DifferenceMetric, Metric};
+use palette::Lab;
+
+// first pair of the CIEDE2000 test data by Sharma, Wu and Dalal (2005)
+fn sharma_pair() -> (Lab, Lab) {
+    (
+        Lab::new(50.0, 2.6772, -79.7751),
+        Lab::new(50.0, 0.0, -82.7485),
+    )
+}
+
+#[test]
+fn reference_values() {
+    let (a, b) = sharma_pair();
+
+    assert!((Metric::Cie76.difference(a, b) - 4.0011).abs() < 1e-3);
+    assert!((Metric::Ciede2000.difference(a, b) - 2.0425).abs() < 1e-3);
+    assert!((Metric::Cie94GraphicArts.difference(a, b) - 1.3950).abs() < 1e-3);
+}
+
+#[test]
+fn identical_colors_have_no_difference() {
+    let color = Lab::new(62.0, -20.0, 35.0);
+    for metric in Metric::ALL {
+        let d = metric.difference(color, color);
+        assert!(d.abs() < 1e-3, "{} gave {}", metric.name(), d);
+        assert_eq!(Metric::from_key(metric.key()), Some(metric));
+    }
+}
+
+#[test]
+fn metrics_agree_on_ordering() {
+    let target = Lab::new(55.0, 30.0, -10.0);
+    let near = Lab::new(56.0, 31.0, -9.0);
+    let far = Lab::new(40.0, 10.0, 20.0);
+    for metric in Metric::ALL {
+        assert!(
+            metric.difference(target, near) < metric.difference(target, far),
+            "{}",
+            metric.name()
+        );
+    }
+}
+
+#[test]
+fn breakdown_matches_ciede2000() {
+    let pairs = [
+        sharma_pair(),
+        (Lab::new(50.0, 2.5, 0.0), Lab::new(50.0, 0.0, -2.5)),
+        (
+            Lab::new(60.2574, -34.0099, 36.2677),
+            Lab::new(60.4626, -34.1751, 39.4387),
+        ),
+        (
+            Lab::new(22.7233, 20.0904, -46.694),
+            Lab::new(23.0331, 14.973, -42.5619),
+        ),
+    ];
+
+    for (a, b) in pairs {
+        let breakdown = Ciede2000Breakdown::new(a, b);
+        assert!((breakdown.delta_e - Metric::Ciede2000.difference(a, b)).abs() < 1e-3);
+    }
+}
+
+#[test]
+fn breakdown_signs() {
+    let target = Lab::new(50.0, 40.0, 20.0);
+
+    let darker = Ciede2000Breakdown::new(target, Lab::new(40.0, 40.0, 20.0));
+    assert!(darker.delta_l < 0.0);
+    assert!(darker.delta_c.abs() < 1e-3 && darker.delta_h.abs() < 1e-3);
+
+    let duller = Ciede2000Breakdown::new(target, Lab::new(50.0, 20.0, 10.0));
+    assert!(duller.delta_c < 0.0);
+    assert!(duller.delta_l.abs() < 1e-3 && duller.delta_h.abs() < 1e-3);
+}
@@ -0,0 +1,86 @@
+use std::time::Duration;
+
+use color_recall::{
+    game::{ChallengeSeed, ColorChallenge},
+    phase::{GamePhase, GameRound, ManualClock, DEFAULT_DELAY},
+};
+use palette::Srgb;
+
+#[test]
+fn round_walks_through_phases() {
+    let clock = ManualClock::new();
+    let challenge = ColorChallenge::from_seed(ChallengeSeed(7));
+    let target = *challenge.target_color();
+    let mut round = GameRound::new(challenge, &clock);
+
+    assert_eq!(round.phase(), GamePhase::Memorize);
+    assert_eq!(round.remaining_delay(), DEFAULT_DELAY);
+    assert_eq!(round.target().unwrap(), &target);
+    assert!(round.submit(target).is_err());
+
+    clock.advance(Duration::from_secs(3));
+    assert_eq!(round.finish_memorizing().unwrap(), Duration::from_secs(3));
+    assert_eq!(round.phase(), GamePhase::Delay);
+    assert!(round.finish_memorizing().is_err());
+    assert!(round.set_delay(Duration::ZERO).is_err());
+
+    clock.advance(Duration::from_secs(5));
+    assert_eq!(round.phase(), GamePhase::Delay);
+    assert_eq!(round.remaining_delay(), Duration::from_secs(3));
+    assert!(round.submit(target).is_err());
+
+    clock.advance(Duration::from_secs(3));
+    assert_eq!(round.phase(), GamePhase::Recall);
+    assert_eq!(round.remaining_delay(), Duration::ZERO);
+
+    clock.advance(Duration::from_secs(12));
+    assert!(round.result("srgb").is_none());
+    assert!(round.submit(target).unwrap() < 1e-3);
+    assert_eq!(round.phase(), GamePhase::Scored);
+    assert!(round.submit(target).is_err());
+
+    let result = round.result("srgb").unwrap();
+    assert_eq!(result.memorize_time, Duration::from_secs(3));
+    assert_eq!(result.recall_time, Duration::from_secs(12));
+    assert_eq!(result.target, target);
+}
+
+#[test]
+fn target_hidden_until_scored() {
+    let clock = ManualClock::new();
+    let mut round = GameRound::with_delay(
+        ColorChallenge::from_seed(ChallengeSeed(7)),
+        &clock,
+        Duration::from_secs(2),
+    );
+
+    round.finish_memorizing().unwrap();
+    assert!(round.target().is_err());
+    assert!(round.challenge().is_err());
+
+    clock.advance(Duration::from_secs(2));
+    assert_eq!(round.phase(), GamePhase::Recall);
+    let err = round.target().unwrap_err();
+    assert_eq!(err.phase, GamePhase::Recall);
+    assert_eq!(
+        err.to_string(),
+        "cannot reveal the target during the recall phase"
+    );
+
+    round.submit(Srgb::new(0.5, 0.5, 0.5)).unwrap();
+    assert!(round.target().is_ok());
+    assert!(round.challenge().is_ok());
+}
+
+#[test]
+fn huge_delay_never_ends() {
+    let clock = ManualClock::new();
+    clock.advance(Duration::from_secs(1));
+    let challenge = ColorChallenge::from_seed(ChallengeSeed(7));
+    let mut round = GameRound::with_delay(challenge, &clock, Duration::MAX);
+
+    round.finish_memorizing().unwrap();
+    clock.advance(Duration::from_secs(3600));
+    assert_eq!(round.phase(), GamePhase::Delay);
+    assert!(round.remaining_delay() > Duration::from_secs(3600));
+}
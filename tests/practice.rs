@@ -0,0 +1,149 @@
+use color_recall::{
+    css::CssColorError,
+    exclusion::ExclusionPreset,
+    game::{ChallengeSeed, ColorChallenge, ExcludeReason},
+    gamut::is_in_gamut,
+    practice::{PaletteEntry, PaletteError, PracticePalette},
+};
+use palette::Srgb;
+
+const BRAND: &str = "
+/* brand colors */
+:root {
+    --brand-red: #c8102e;
+    --brand-blue: oklch(0.45 0.15 260);
+    --gap: 4px;
+    --accent: var(--brand-red);
+    --paper: rgb(250 248 240) !important;
+}
+";
+
+#[test]
+fn custom_targets_are_kept() {
+    let target = Srgb::new(0.02, 0.02, 0.02);
+    let challenge = ColorChallenge::from_color(target, ChallengeSeed(7));
+    assert!(challenge.is_custom());
+    assert_eq!(*challenge.target_color(), target);
+    assert_eq!(challenge.seed().0, 7);
+    assert_eq!(
+        ColorChallenge::is_excluded(&target),
+        Some(ExcludeReason::TooDark)
+    );
+    assert_eq!(challenge.compute_distance(target), 0.0);
+
+    assert!(!ColorChallenge::from_seed(ChallengeSeed(7)).is_custom());
+}
+
+#[test]
+fn css_variables() {
+    let palette = PracticePalette::from_css_variables(BRAND).unwrap();
+    let names: Vec<&str> = palette.entries().iter().map(|e| e.name.as_str()).collect();
+    assert_eq!(names, ["brand-red", "brand-blue", "paper"]);
+
+    let red = palette.entries()[0].color;
+    assert!((red.red - 200.0 / 255.0).abs() < 1e-6);
+
+    assert_eq!(
+        PracticePalette::from_css_variables("--gap: 4px; --oops: #12345;"),
+        Err(PaletteError::InvalidColor {
+            name: "oops".to_string(),
+            error: CssColorError::InvalidHex("#12345".to_string()),
+        })
+    );
+    assert_eq!(
+        PracticePalette::from_css_variables("--gap: 4px;"),
+        Err(PaletteError::Empty)
+    );
+}
+
+#[cfg(feature = "serde")]
+#[test]
+fn json() {
+    let palette =
+        PracticePalette::from_json(r##"{"brand-red": "#c8102e", "sky": "hsl(200 80% 60%)"}"##)
+            .unwrap();
+    assert_eq!(palette.len(), 2);
+    assert!(palette.entries().iter().any(|e| e.name == "sky"));
+
+    let palette = PracticePalette::from_json(r##"["#c8102e", "#0033a0"]"##).unwrap();
+    assert_eq!(palette.entries()[1].name, "#0033a0");
+
+    assert!(matches!(
+        PracticePalette::from_json("[1, 2]"),
+        Err(PaletteError::Json(_))
+    ));
+    assert!(matches!(
+        PracticePalette::from_json("{\"a\": \"#zz\"}"),
+        Err(PaletteError::InvalidColor { .. })
+    ));
+    assert_eq!(PracticePalette::from_json("[]"), Err(PaletteError::Empty));
+
+    let serialized = serde_json::to_string(&palette).unwrap();
+    assert_eq!(
+        serde_json::from_str::<PracticePalette>(&serialized).unwrap(),
+        palette
+    );
+    assert!(serde_json::from_str::<PracticePalette>(r#"{"entries":[]}"#).is_err());
+
+    assert_eq!(
+        PracticePalette::from_json(&palette.to_json().unwrap()).unwrap(),
+        palette
+    );
+    assert!(PracticePalette::from_json(r#"{"entries":[]}"#).is_err());
+    let wide = PracticePalette::from_json(
+        r#"{"entries":[{"name":"p3","color":{"red":-0.3,"green":1.1,"blue":0.2}}]}"#,
+    )
+    .unwrap();
+    assert!(is_in_gamut(&wide.entries()[0].color));
+}
+
+#[test]
+fn wide_gamut_entries_are_mapped_into_srgb() {
+    let entry = PaletteEntry::new("p3", Srgb::new(-0.3, 1.1, 0.2));
+    assert!(is_in_gamut(&entry.color));
+}
+
+#[test]
+fn excluded_entries_are_reported() {
+    let palette = PracticePalette::new(vec![
+        PaletteEntry::new("ink", Srgb::new(0.02, 0.02, 0.03)),
+        PaletteEntry::new("orange", Srgb::new(0.8, 0.4, 0.2)),
+        PaletteEntry::new("gray", Srgb::new(0.5, 0.5, 0.5)),
+    ])
+    .unwrap();
+
+    let excluded = palette.excluded(&ExclusionPreset::Default);
+    let names: Vec<&str> = excluded.iter().map(|(e, _)| e.name.as_str()).collect();
+    assert_eq!(names, ["ink", "gray"]);
+    assert!(palette.excluded(&ExclusionPreset::None).is_empty());
+}
+
+#[test]
+fn draws_are_reproducible_and_cover_the_palette() {
+    let palette = PracticePalette::from_css_variables(BRAND).unwrap();
+    let mut seen = vec![false; palette.len()];
+    for seed in ChallengeSeed(1).sequence().take(100) {
+        let challenge = palette.challenge(seed);
+        assert!(challenge.is_custom());
+        assert_eq!(challenge.seed().0, seed.0);
+        assert_eq!(
+            palette.challenge(seed).target_color(),
+            challenge.target_color()
+        );
+
+        let index = palette
+            .entries()
+            .iter()
+            .position(|e| e.color == *challenge.target_color())
+            .unwrap();
+        seen[index] = true;
+    }
+    assert!(seen.iter().all(|s| *s));
+
+    let strict = palette.challenge_with(ChallengeSeed(1), ExclusionPreset::Strict);
+    assert_eq!(strict.exclusion(), ExclusionPreset::Strict);
+    assert_eq!(
+        strict.target_color(),
+        palette.challenge(ChallengeSeed(1)).target_color()
+    );
+}
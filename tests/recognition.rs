@@ -0,0 +1,118 @@
+use color_recall::{
+    exclusion::{ExclusionPolicy, ExclusionPreset},
+    game::{color_at_distance, ChallengeSeed, ColorChallenge},
+    gamut::is_in_gamut,
+    metric::{Cie76, Ciede2000, DifferenceMetric},
+    phase::{GamePhase, GameRound, ManualClock, DEFAULT_DELAY},
+    recognition::{RecognitionError, RecognitionRound, RecognitionStats, RecognitionTrial},
+};
+use palette::{IntoColor, Lab, Srgb};
+use rand::SeedableRng;
+use rand_chacha::ChaCha8Rng;
+
+#[test]
+fn colors_at_exact_distance() {
+    let mut rng = ChaCha8Rng::seed_from_u64(17);
+
+    for seed in 0..20 {
+        let challenge = ColorChallenge::from_seed(ChallengeSeed(seed));
+        let target: Lab = (*challenge.target_color()).into_color();
+
+        for delta_e in [0.5, 2.0, 5.0, 10.0] {
+            let color = challenge.distractor(&mut rng, delta_e).unwrap();
+            let lab: Lab = color.into_color();
+
+            assert!((Ciede2000.difference(target, lab) - delta_e).abs() < 0.02);
+            assert!(is_in_gamut(&color));
+            assert!(ExclusionPreset::Default.check(&color).is_none());
+        }
+    }
+
+    let gray = Srgb::new(0.5, 0.5, 0.5);
+    let color = color_at_distance(&mut rng, &gray, 20.0, &Cie76, &ExclusionPreset::None).unwrap();
+    let lab: Lab = color.into_color();
+    assert!((Cie76.difference(gray.into_color(), lab) - 20.0).abs() < 0.02);
+
+    assert!(color_at_distance(&mut rng, &gray, 500.0, &Cie76, &ExclusionPreset::None).is_none());
+    assert!(color_at_distance(&mut rng, &gray, 0.0, &Cie76, &ExclusionPreset::None).is_none());
+}
+
+#[test]
+fn recognition_round_hides_target_among_distractors() {
+    let mut rng = ChaCha8Rng::seed_from_u64(3);
+    let challenge = ColorChallenge::from_seed(ChallengeSeed(3));
+
+    let round = RecognitionRound::generate(&mut rng, &challenge, 4, 3.0).unwrap();
+    assert_eq!(round.options().len(), 4);
+    assert_eq!(round.options()[round.answer()], *challenge.target_color());
+    assert!(round.check(round.answer()).correct);
+    assert!(!round.check((round.answer() + 1) % 4).correct);
+    assert_eq!(round.check(0).alternatives, 4);
+
+    assert!(RecognitionRound::generate(&mut rng, &challenge, 1, 3.0).is_none());
+}
+
+#[test]
+fn recognition_options_are_drawn_once_per_round() {
+    let mut rng = ChaCha8Rng::seed_from_u64(5);
+    let clock = ManualClock::new();
+    let mut round = GameRound::new(ColorChallenge::from_seed(ChallengeSeed(5)), &clock);
+    assert!(matches!(
+        round.recognition_round(&mut rng, 4, 3.0),
+        Err(RecognitionError::WrongPhase(_))
+    ));
+
+    round.finish_memorizing().unwrap();
+    clock.advance(DEFAULT_DELAY);
+    assert_eq!(round.phase(), GamePhase::Recall);
+    assert!(round.recognition().is_none());
+
+    let first = round.recognition_round(&mut rng, 4, 3.0).unwrap().clone();
+    for _ in 0..3 {
+        let again = round.recognition_round(&mut rng, 4, 3.0).unwrap();
+        assert_eq!(again.options(), first.options());
+    }
+    assert_eq!(
+        round.recognition_round(&mut rng, 6, 8.0).unwrap_err(),
+        RecognitionError::AlreadyDrawn {
+            alternatives: 4,
+            delta_e: 3.0
+        }
+    );
+    assert_eq!(round.recognition().unwrap().answer(), first.answer());
+}
+
+#[test]
+fn recognition_threshold() {
+    let trial = |delta_e, correct| RecognitionTrial {
+        delta_e,
+        alternatives: 4,
+        correct,
+    };
+    // 25% at ΔE 1, 75% at ΔE 3, 100% at ΔE 5
+    let trials = [
+        trial(1.0, true),
+        trial(1.0, false),
+        trial(1.0, false),
+        trial(1.0, false),
+        trial(3.0, true),
+        trial(3.0, true),
+        trial(3.0, true),
+        trial(3.0, false),
+        trial(5.0, true),
+        trial(5.0, true),
+    ];
+
+    let stats = RecognitionStats::from_trials(&trials).unwrap();
+    assert_eq!(stats.levels.len(), 3);
+    assert_eq!(stats.levels[1].accuracy(), 0.75);
+    assert_eq!(stats.chance, 0.25);
+    assert_eq!(stats.criterion(), 0.625);
+    assert!((stats.threshold().unwrap() - 2.5).abs() < 1e-5);
+
+    assert!(RecognitionStats::from_trials(&trials[..4])
+        .unwrap()
+        .threshold()
+        .is_none());
+    assert!(RecognitionStats::from_trials(&[]).is_none());
+}
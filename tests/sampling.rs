@@ -0,0 +1,135 @@
+use color_recall::{
+    exclusion::{ExclusionPolicy, ExclusionPreset},
+    game::{ChallengeSeed, ColorChallenge},
+    sampling::{TargetDistribution, MAX_SAMPLE_ATTEMPTS},
+};
+use palette::{IntoColor, Lch, Srgb};
+
+const SAMPLES: usize = 20_000;
+
+fn samples_with(distribution: TargetDistribution, policy: ExclusionPreset) -> Vec<Srgb> {
+    let mut rng = ChallengeSeed(0xc010_5eed).rng();
+    (0..SAMPLES)
+        .map(|_| {
+            distribution
+                .sample(&mut rng, &policy)
+                .expect("sampler gave up")
+        })
+        .collect()
+}
+
+fn samples(distribution: TargetDistribution) -> Vec<Srgb> {
+    samples_with(distribution, ExclusionPreset::Default)
+}
+
+fn histogram(values: impl Iterator<Item = f32>, min: f32, max: f32, bins: usize) -> Vec<usize> {
+    let mut hist = vec![0; bins];
+    for v in values {
+        let i = (((v - min) / (max - min)) * bins as f32) as usize;
+        hist[i.min(bins - 1)] += 1;
+    }
+    hist
+}
+
+#[test]
+fn samples_are_allowed() {
+    for distribution in [
+        TargetDistribution::UniformSrgb,
+        TargetDistribution::UniformLab,
+        TargetDistribution::UniformOklab,
+    ] {
+        for policy in ExclusionPreset::ALL {
+            for color in samples_with(distribution, policy) {
+                assert!(policy.check(&color).is_none());
+                for c in [color.red, color.green, color.blue] {
+                    assert!((0.0..=1.0).contains(&c));
+                }
+            }
+        }
+    }
+}
+
+#[test]
+fn hue_coverage() {
+    for distribution in [
+        TargetDistribution::UniformLab,
+        TargetDistribution::UniformOklab,
+    ] {
+        let hues = samples(distribution).into_iter().map(|c| {
+            let lch: Lch = c.into_color();
+            lch.hue.into_positive_degrees()
+        });
+        let hist = histogram(hues, 0.0, 360.0, 12);
+
+        // every 30° sector is represented with at least a quarter of its fair share
+        for (i, count) in hist.iter().enumerate() {
+            assert!(
+                *count > SAMPLES / 12 / 4,
+                "{:?} hue bin {} has {} samples: {:?}",
+                distribution,
+                i,
+                count,
+                hist
+            );
+        }
+    }
+}
+
+#[test]
+fn lightness_coverage() {
+    for distribution in [
+        TargetDistribution::UniformLab,
+        TargetDistribution::UniformOklab,
+    ] {
+        let lightness = samples(distribution).into_iter().map(|c| {
+            let lch: Lch = c.into_color();
+            lch.l
+        });
+        let hist = histogram(lightness, 0.0, 100.0, 10);
+
+        // the mid tones between L* 20 and 80 are all well covered
+        for (i, count) in hist.iter().enumerate().take(8).skip(2) {
+            assert!(
+                *count > SAMPLES / 10 / 4,
+                "{:?} lightness bin {} has {} samples: {:?}",
+                distribution,
+                i,
+                count,
+                hist
+            );
+        }
+    }
+}
+
+#[test]
+fn seeded_sampling_is_deterministic() {
+    let seed = ChallengeSeed(99);
+    for distribution in [
+        TargetDistribution::UniformSrgb,
+        TargetDistribution::UniformLab,
+        TargetDistribution::UniformOklab,
+    ] {
+        let a = ColorChallenge::from_seed_with(seed, distribution, ExclusionPreset::Default);
+        let b = ColorChallenge::from_seed_with(seed, distribution, ExclusionPreset::Default);
+        assert_eq!(a.target_color(), b.target_color());
+    }
+}
+
+#[test]
+fn sampling_never_gives_up() {
+    let mut rng = ChallengeSeed(0xacce97).rng();
+    for distribution in TargetDistribution::ALL {
+        for policy in ExclusionPreset::ALL {
+            let rate = distribution.acceptance_rate(&mut rng, &policy, SAMPLES);
+            // the chance of falling back to a fixed target instead of a sampled one
+            let give_up = (1.0 - rate as f64).powi(MAX_SAMPLE_ATTEMPTS as i32);
+            assert!(
+                give_up < 1e-15,
+                "{:?} {:?} accepts {}",
+                distribution,
+                policy,
+                rate
+            );
+        }
+    }
+}
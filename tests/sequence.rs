@@ -0,0 +1,164 @@
+use std::time::Duration;
+
+use color_recall::{
+    exclusion::{ExclusionPolicy, ExclusionPreset},
+    game::ChallengeSeed,
+    metric::{Ciede2000, DifferenceMetric},
+    phase::{GamePhase, ManualClock},
+    sampling::TargetDistribution,
+    sequence::{
+        Matching, Presentation, SequenceChallenge, SequenceRound, MAX_COLORS, MIN_COLORS,
+        MIN_SEPARATION,
+    },
+};
+use palette::{IntoColor, Lab, Srgb};
+
+#[test]
+fn sequences_are_reproducible_and_separated() {
+    for seed in 0..20 {
+        let challenge = SequenceChallenge::from_seed(ChallengeSeed(seed), 5).unwrap();
+        assert_eq!(challenge.len(), 5);
+        assert_eq!(
+            challenge.targets(),
+            SequenceChallenge::from_seed(ChallengeSeed(seed), 5)
+                .unwrap()
+                .targets()
+        );
+
+        for (i, a) in challenge.targets().iter().enumerate() {
+            assert!(ExclusionPreset::Default.check(a).is_none());
+            for b in &challenge.targets()[i + 1..] {
+                let a: Lab = (*a).into_color();
+                let b: Lab = (*b).into_color();
+                assert!(Ciede2000.difference(a, b) >= MIN_SEPARATION);
+            }
+        }
+    }
+
+    let strict = SequenceChallenge::from_seed_with(
+        ChallengeSeed(1),
+        3,
+        TargetDistribution::UniformOklab,
+        ExclusionPreset::Strict,
+    )
+    .unwrap();
+    assert_eq!(strict.exclusion(), ExclusionPreset::Strict);
+
+    assert!(SequenceChallenge::from_seed(ChallengeSeed(1), MIN_COLORS - 1).is_none());
+    assert!(SequenceChallenge::from_seed(ChallengeSeed(1), MAX_COLORS).is_some());
+    assert!(SequenceChallenge::from_seed(ChallengeSeed(1), MAX_COLORS + 1).is_none());
+}
+
+#[test]
+fn ordered_and_best_match_scoring() {
+    let challenge = SequenceChallenge::from_seed(ChallengeSeed(7), 4).unwrap();
+    let targets = challenge.targets().to_vec();
+
+    let exact = challenge.score(&targets, Matching::Ordered).unwrap();
+    assert_eq!(exact.distances.len(), 4);
+    assert!(exact.total() < 1e-3);
+    assert_eq!(exact.assignment, vec![0, 1, 2, 3]);
+
+    let mut reversed = targets.clone();
+    reversed.reverse();
+    let ordered = challenge.score(&reversed, Matching::Ordered).unwrap();
+    assert!(ordered.mean() > MIN_SEPARATION);
+    let matched = challenge.score(&reversed, Matching::BestMatch).unwrap();
+    assert!(matched.total() < 1e-3);
+    assert_eq!(matched.assignment, vec![3, 2, 1, 0]);
+
+    assert!(challenge.score(&targets[1..], Matching::Ordered).is_none());
+}
+
+#[test]
+fn best_match_minimizes_total() {
+    let challenge = SequenceChallenge::from_seed(ChallengeSeed(11), 3).unwrap();
+    let guesses = [
+        Srgb::new(0.5, 0.5, 0.5),
+        Srgb::new(0.9, 0.2, 0.2),
+        Srgb::new(0.2, 0.3, 0.8),
+    ];
+
+    let best = challenge
+        .score_with(&Ciede2000, &guesses, Matching::BestMatch)
+        .unwrap();
+    let mut assignment = best.assignment.clone();
+    assignment.sort();
+    assert_eq!(assignment, vec![0, 1, 2]);
+
+    // no pairing does better than the one found
+    let permutations = [
+        [0, 1, 2],
+        [0, 2, 1],
+        [1, 0, 2],
+        [1, 2, 0],
+        [2, 0, 1],
+        [2, 1, 0],
+    ];
+    for permutation in permutations {
+        let total: f32 = permutation
+            .iter()
+            .enumerate()
+            .map(|(target, guess)| {
+                let target: Lab = challenge.targets()[target].into_color();
+                Ciede2000.difference(target, guesses[*guess].into_color())
+            })
+            .sum();
+        assert!(best.total() <= total + 1e-4);
+    }
+}
+
+#[test]
+fn presentation_keys_roundtrip() {
+    for presentation in Presentation::ALL {
+        assert_eq!(
+            Presentation::from_key(presentation.key()),
+            Some(presentation)
+        );
+    }
+    assert_eq!(Presentation::from_key("shuffled"), None);
+}
+
+#[test]
+fn together_round_shows_all_targets() {
+    let clock = ManualClock::new();
+    let challenge = SequenceChallenge::from_seed(ChallengeSeed(3), 3).unwrap();
+    let targets = challenge.targets().to_vec();
+    let mut round = SequenceRound::with_delay(challenge, &clock, Duration::from_secs(2));
+
+    assert_eq!(round.shown().unwrap(), (0, &targets[..]));
+    round.finish_memorizing().unwrap();
+    assert_eq!(round.phase(), GamePhase::Delay);
+    assert!(round.shown().is_err());
+    assert!(round.submit(targets[0]).is_err());
+
+    clock.advance(Duration::from_secs(2));
+    for (i, target) in targets.iter().enumerate() {
+        assert_eq!(round.phase(), GamePhase::Recall);
+        assert_eq!(round.recalling(), i);
+        assert!(round.score(Matching::Ordered).is_none());
+        round.submit(*target).unwrap();
+    }
+
+    assert_eq!(round.phase(), GamePhase::Scored);
+    assert_eq!(round.guesses(), &targets[..]);
+    assert!(round.score(Matching::Ordered).unwrap().total() < 1e-3);
+    assert_eq!(round.challenge().unwrap().targets(), &targets[..]);
+    assert!(round.submit(targets[0]).is_err());
+}
+
+#[test]
+fn sequential_round_shows_one_target_at_a_time() {
+    let clock = ManualClock::new();
+    let mut challenge = SequenceChallenge::from_seed(ChallengeSeed(3), 3).unwrap();
+    challenge.set_presentation(Presentation::Sequential);
+    let targets = challenge.targets().to_vec();
+    let mut round = SequenceRound::new(challenge, &clock);
+
+    for (i, target) in targets.iter().enumerate() {
+        assert_eq!(round.phase(), GamePhase::Memorize);
+        assert_eq!(round.shown().unwrap(), (i, std::slice::from_ref(target)));
+        round.finish_memorizing().unwrap();
+    }
+    assert_eq!(round.phase(), GamePhase::Delay);
+}
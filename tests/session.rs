@@ -0,0 +1,171 @@
+use std::time::Duration;
+
+use color_recall::{
+    cvd::{CvdSimulation, Deficiency},
+    game::{ChallengeSeed, ColorChallenge},
+    metric::Metric,
+    session::{GameSession, RoundResult, SessionStats},
+};
+
+#[test]
+fn stats_from_scores() {
+    let stats = SessionStats::from_scores(&[4.0, 2.0, 8.0, 6.0]).unwrap();
+    assert_eq!(stats.count, 4);
+    assert_eq!(stats.mean, 5.0);
+    assert_eq!(stats.median, 5.0);
+    assert_eq!(stats.best, 2.0);
+    assert_eq!(stats.worst, 8.0);
+    assert!((stats.std_dev - 2.5819888).abs() < 1e-5);
+
+    let single = SessionStats::from_scores(&[3.0]).unwrap();
+    assert_eq!(single.median, 3.0);
+    assert_eq!(single.std_dev, 0.0);
+
+    assert!(SessionStats::from_scores(&[]).is_none());
+}
+
+#[test]
+fn session_records_rounds() {
+    let mut session = GameSession::new();
+    assert!(session.stats().is_none());
+
+    for seed in 0..3 {
+        let challenge = ColorChallenge::from_seed(ChallengeSeed(seed));
+        let guess = *challenge.target_color();
+        session.push(RoundResult::new(
+            &challenge,
+            guess,
+            "srgb",
+            Duration::from_secs(5),
+            Duration::from_secs(20),
+        ));
+    }
+
+    assert_eq!(session.len(), 3);
+    let stats = session.stats().unwrap();
+    assert!(stats.worst < 1e-3);
+    assert!(session.stats_with(&Metric::Cie76).unwrap().worst < 1e-3);
+}
+
+#[test]
+fn stats_use_one_metric() {
+    let mut session = GameSession::new();
+    for (seed, metric) in [(1, Metric::Cie76), (2, Metric::DeltaEItp)] {
+        let mut challenge = ColorChallenge::from_seed(ChallengeSeed(seed));
+        challenge.set_metric(metric);
+        session.push(RoundResult::new(
+            &challenge,
+            palette::Srgb::new(0.5, 0.5, 0.5),
+            "srgb",
+            Duration::from_secs(5),
+            Duration::from_secs(20),
+        ));
+    }
+
+    assert_eq!(session.stats(), session.stats_with(&Metric::DeltaEItp));
+    assert_ne!(session.stats(), session.stats_with(&Metric::Cie76));
+}
+
+#[test]
+fn csv_export() {
+    let mut session = GameSession::new();
+    let challenge = ColorChallenge::from_seed(ChallengeSeed(42));
+    session.push(RoundResult::new(
+        &challenge,
+        *challenge.target_color(),
+        "lch",
+        Duration::from_millis(1500),
+        Duration::from_millis(12000),
+    ));
+
+    let csv = session.to_csv();
+    let mut lines = csv.lines();
+    assert!(lines.next().unwrap().starts_with("seed,model,metric,"));
+    let row = lines.next().unwrap();
+    assert!(row.starts_with("000000000000002a,lch,ciede2000_improved,"));
+    assert!(row.ends_with(",1500,12000"));
+    assert!(lines.next().is_none());
+
+    let mut round = session.rounds()[0].clone();
+    round.model = "a, \"b\"\nc".to_string();
+    let mut quoted = GameSession::new();
+    quoted.push(round);
+    let csv = quoted.to_csv();
+    assert!(csv.contains(",\"a, \"\"b\"\"\nc\",ciede2000_improved,"));
+}
+
+#[test]
+fn custom_targets_have_no_seed() {
+    let challenge =
+        ColorChallenge::from_color(palette::Srgb::new(0.8, 0.4, 0.2), ChallengeSeed(42));
+    let mut session = GameSession::new();
+    session.push(RoundResult::new(
+        &challenge,
+        palette::Srgb::new(0.5, 0.5, 0.5),
+        "srgb",
+        Duration::from_secs(5),
+        Duration::from_secs(20),
+    ));
+
+    assert_eq!(session.rounds()[0].seed, None);
+    assert!(session
+        .to_csv()
+        .lines()
+        .nth(1)
+        .unwrap()
+        .starts_with(",srgb,"));
+}
+
+#[test]
+fn simulated_scoring_is_recorded() {
+    let mut challenge = ColorChallenge::from_seed(ChallengeSeed(42));
+    let simulation = CvdSimulation::dichromacy(Deficiency::Deutan);
+    challenge.set_simulation(Some(simulation));
+    let round = RoundResult::new(
+        &challenge,
+        palette::Srgb::new(0.5, 0.5, 0.5),
+        "srgb",
+        Duration::from_secs(5),
+        Duration::from_secs(20),
+    );
+    assert_eq!(round.simulation, Some(simulation));
+    assert_eq!(round.score, challenge.compute_distance(round.guess));
+    assert!((round.score_with(&round.metric) - round.score).abs() < 1e-3);
+    let unsimulated = ColorChallenge::from_seed(ChallengeSeed(42)).compute_distance(round.guess);
+    assert!((round.score - unsimulated).abs() > 1.0);
+
+    let mut session = GameSession::new();
+    session.push(round.clone());
+    let stats = session.stats().unwrap();
+    assert!((stats.mean - round.score).abs() < 1e-3);
+    let csv = session.to_csv();
+    assert!(csv.starts_with("seed,model,metric,cvd,cvd_severity,"));
+    assert!(csv.lines().nth(1).unwrap().contains(",deutan,1,"));
+}
+
+#[cfg(feature = "serde")]
+#[test]
+fn json_roundtrip() {
+    let mut session = GameSession::new();
+    let challenge = ColorChallenge::from_seed(ChallengeSeed(7));
+    session.push(RoundResult::new(
+        &challenge,
+        palette::Srgb::new(0.2, 0.4, 0.6),
+        "hsv",
+        Duration::from_secs(3),
+        Duration::from_secs(9),
+    ));
+
+    let json = session.to_json().unwrap();
+    assert!(json.contains("\"0000000000000007\""));
+    assert!(json.contains("\"ciede2000_improved\""));
+
+    let restored = GameSession::from_json(&json).unwrap();
+    assert_eq!(restored.len(), 1);
+    let (a, b) = (&session.rounds()[0], &restored.rounds()[0]);
+    assert_eq!(a.seed, b.seed);
+    assert_eq!(a.guess, b.guess);
+    assert_eq!(a.metric, b.metric);
+    assert_eq!(a.score, b.score);
+    assert_eq!(a.recall_time, b.recall_time);
+}